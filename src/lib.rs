@@ -1,4 +1,6 @@
+#![allow(non_snake_case)]     //the crate is called FaksLife (the wasm file name depends on it) so we keep the name
 mod models;
+mod minigames;
 mod msg;
 mod update;
 mod view;
@@ -38,6 +40,7 @@ impl Application for Model {
 
 #[wasm_bindgen(start)]      //so that the function start() runs immediately when the game generates
 pub fn start() {
+    let _ = console_log::init_with_level(log::Level::Warn);     //log::warn! (ex. broken exam questions) shows up in the browser console
     let program = Program::mount_to_body(Model {        //creates the app and attaches it to (the body of) HTML
         game_state: GameState::new(),});        //we start with a fresh gamestate. new() is a function (in gamestate mod) that sets everything to default (ex. screen::Start)
 
@@ -0,0 +1,41 @@
+//small challenges that take over the whole screen (exam sheet, getting out of bed...)
+//update and view just hand everything to the active mini-game, so adding a new one means a new file here and a new MiniGameResult
+
+pub mod wake_up;
+pub mod written_exam;
+
+use crate::models::audio::Track;
+use crate::models::exam::Subject;
+use crate::models::gamestate::GameState;
+use crate::msg::Msg;
+use sauron::prelude::*;
+
+pub trait MiniGame {
+    //called once when the mini-game starts, before the first Tick (ex. to read the player's meters)
+    fn init(&mut self, _game_state: &mut GameState) {}
+
+    //every Tick, key press and Msg::MiniGameClick while the mini-game is on screen
+    fn update(&mut self, msg: &Msg, game_state: &mut GameState);
+
+    fn view(&self, game_state: &GameState) -> Node<Msg>;
+
+    //which music plays during it, None keeps the room's
+    fn music(&self) -> Option<Track> {
+        None
+    }
+
+    //Some once the mini-game is over, GameState::finish_minigame takes it from there
+    fn result(&self) -> Option<MiniGameResult>;
+}
+
+pub enum MiniGameResult {       //what a mini-game hands back to the main game
+    WakeUp {
+        minutes_late: u32,
+        anxiety: i32,       //starting anxiety for the day
+    },
+    WrittenExam {
+        subject: Subject,
+        percent: u32,
+        passed: bool,
+    },
+}
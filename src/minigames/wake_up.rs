@@ -0,0 +1,86 @@
+//jutro po prečuti noči as a mini-game, the clicking itself is in models/wake_up.rs
+
+use crate::minigames::{MiniGame, MiniGameResult};
+use crate::models::gamestate::GameState;
+use crate::models::input::Action;
+use crate::models::wake_up::{self, WakeUp};
+use crate::msg::Msg;
+use sauron::prelude::*;
+
+impl MiniGame for WakeUp {
+    fn update(&mut self, msg: &Msg, game_state: &mut GameState) {
+        match msg {
+            Msg::MiniGameClick => self.click(),
+            Msg::KeyDown(_) | Msg::Pressed(_) if game_state.bindings.pressed(msg).contains(&Action::Confirm) => self.click(),     //mashing A on a controller works too
+            Msg::Tick => {
+                let new_minute = self.tick();
+                if new_minute {
+                    game_state.clock.advance_minutes(1);        //the clock keeps running while you're in bed
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn view(&self, game_state: &GameState) -> Node<Msg> {      //dark bedroom with a ringing alarm, click anywhere
+        let clock = &game_state.clock;
+        let content = if self.finished {
+            vec![
+                div([style! { "font-size": "22px", "margin-bottom": "12px" }], [text(game_state.say("[Vstal|Vstala] si!"))]),
+                div([], [text(format!("Zamuda: {} min", self.minutes_late))]),
+                div([style! { "margin": "8px 0 12px 0" }], [text(format!("Anksioznost za začetek dneva: {}", self.anxiety()))]),
+                div([style! { "font-size": "12px" }], [text(if self.can_continue() { "Klikni (ali pritisni Enter) za nadaljevanje." } else { "..." })]),
+            ]
+        } else {
+            let ratio = self.clicks as f64 / wake_up::CLICKS_TO_WAKE_UP as f64;
+            vec![
+                div([style! { "font-size": "48px", "color": "#ff5a35", "margin-bottom": "12px" }], [text(format!("{:02}:{:02}", clock.hour(), clock.minute()))]),
+                div([style! { "margin-bottom": "12px" }], [text("Budilka zvoni! Klikaj čim hitreje, da vstaneš iz postelje.")]),
+                //how awake you are
+                div(
+                    [style! { "height": "10px", "background": "#493508", "margin": "0 auto", "width": "300px" }],
+                    [div(
+                        [style! {
+                            "height": "100%",
+                            "width": format!("{}%", ratio * 100.0),
+                            "background": "#ffdd35",
+                        }],
+                        [],
+                    )],
+                ),
+                div([style! { "margin-top": "12px", "font-size": "12px", "color": "#ccc" }], [text(format!("Zamuda: {} min", self.minutes_late))]),
+            ]
+        };
+
+        div(
+            [
+                on_keydown(|event: KeyboardEvent| Msg::KeyDown(event.key())),      //Enter (Confirm) continues, same as a click
+                on_keyup(|event: KeyboardEvent| Msg::KeyUp(event.key())),
+                attr("tabindex", "0"),
+                attr("id", "game-root"),
+                style! {
+                    "position": "fixed",
+                    "top": "0",
+                    "left": "0",
+                    "width": "100vw",
+                    "height": "100vh",
+                    "background-color": "#0b0b1a",
+                    "display": "flex",
+                    "align-items": "center",
+                    "justify-content": "center",
+                    "cursor": "pointer",
+                    "user-select": "none",      //fast clicking would otherwise select all the text
+                    "font-family": "monospace",
+                    "color": "white",
+                    "text-align": "center",
+                },
+                on_click(|_| Msg::MiniGameClick),
+            ],
+            [div([], content)],
+        )
+    }
+
+    fn result(&self) -> Option<MiniGameResult> {
+        self.confirmed.then(|| MiniGameResult::WakeUp { minutes_late: self.minutes_late, anxiety: self.anxiety() })
+    }
+}
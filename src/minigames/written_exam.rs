@@ -0,0 +1,129 @@
+//pisni izpit as a mini-game, questions and scoring are in models/exam.rs
+
+use crate::minigames::{MiniGame, MiniGameResult};
+use crate::models::audio::Track;
+use crate::models::exam::{WrittenExam, TICKS_PER_SECOND};
+use crate::models::gamestate::GameState;
+use crate::models::input::Action;
+use crate::msg::Msg;
+use sauron::prelude::*;
+
+impl MiniGame for WrittenExam {
+    fn init(&mut self, game_state: &mut GameState) {
+        let player = &game_state.player;
+        self.start(player.attention, player.max_attention, player.anxiety, player.max_anxiety, &mut game_state.rng);
+    }
+
+    fn update(&mut self, msg: &Msg, game_state: &mut GameState) {      //keys answer questions instead of moving you around
+        let (actions, rng) = (game_state.bindings.pressed(msg), &mut game_state.rng);      //keys and controller buttons alike
+        match msg {
+            Msg::Tick => self.tick(rng),
+            _ if self.finished && (actions.contains(&Action::Confirm) || actions.contains(&Action::Cancel)) => self.hand_in(),
+            _ if self.finished => {}
+            _ if actions.contains(&Action::MoveUp) => self.select_previous(),
+            _ if actions.contains(&Action::MoveDown) => self.select_next(),
+            _ if actions.contains(&Action::Confirm) => self.answer(rng),
+            Msg::KeyDown(key) => {
+                //number keys pick and confirm an answer directly
+                if let Some(n) = key.parse::<usize>().ok().filter(|n| (1..=self.shown.len()).contains(n)) {
+                    self.selection = n - 1;
+                    self.answer(rng);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn view(&self, game_state: &GameState) -> Node<Msg> {     //exam sheet on a dark background, answers styled like the item menu
+        let sheet = if self.finished {
+            vec![
+                div([style! { "font-size": "22px", "margin-bottom": "12px" }], [text(format!("{} - rezultat", self.subject.name()))]),
+                div([], [text(format!("Pravilnih odgovorov: {}/{} ({} %)", self.correct_count, self.questions.len(), self.percent()))]),
+                div([style! { "margin": "12px 0" }], [text(game_state.say(if self.passed() { "[Opravil|Opravila] si!" } else { "[Padel|Padla] si." }))]),
+                div([style! { "font-size": "12px" }], [text(format!("Pritisni {} za nadaljevanje.", game_state.bindings.label(Action::Confirm)))]),
+            ]
+        } else {
+            let question = self.question().map(|q| q.text.as_str()).unwrap_or("");
+            let seconds_left = self.ticks_left.div_ceil(TICKS_PER_SECOND);
+            let time_ratio = self.ticks_left as f64 / self.ticks_per_question as f64;
+
+            vec![
+                div(
+                    [style! { "display": "flex", "justify-content": "space-between", "font-size": "12px" }],
+                    [
+                        text(format!("{} - vprašanje {}/{}", self.subject.name(), self.current + 1, self.questions.len())),
+                        text(format!("{} s", seconds_left)),
+                    ],
+                ),
+                //timer bar
+                div(
+                    [style! { "height": "6px", "background": "#493508", "margin": "6px 0 14px 0" }],
+                    [div(
+                        [style! {
+                            "height": "100%",
+                            "width": format!("{}%", time_ratio * 100.0),
+                            "background": if time_ratio < 0.25 { "#ff5a35" } else { "#ffdd35" },
+                        }],
+                        [],
+                    )],
+                ),
+                div([style! { "font-size": "18px", "margin-bottom": "14px" }], [text(question)]),
+                div(
+                    [],
+                    self.shown.iter().enumerate().map(|(i, answer)| {
+                        let selected = i == self.selection;
+                        div(
+                            [style! {
+                                "padding": "6px 10px",
+                                "margin-bottom": "4px",
+                                "background": if selected { "#ffdd35" } else { "transparent" },
+                                "color": if selected { "black" } else { "white" },
+                            }],
+                            [text(format!("{}) {}", i + 1, answer.text))],
+                        )
+                    }),
+                ),
+            ]
+        };
+
+        div(
+            [
+                on_keydown(|event: KeyboardEvent| Msg::KeyDown(event.key())),
+                on_keyup(|event: KeyboardEvent| Msg::KeyUp(event.key())),
+                attr("tabindex", "0"),
+                attr("id", "game-root"),
+                style! {
+                    "position": "fixed",
+                    "top": "0",
+                    "left": "0",
+                    "width": "100vw",
+                    "height": "100vh",
+                    "outline": "none",
+                    "background-color": "black",
+                    "display": "flex",
+                    "align-items": "center",
+                    "justify-content": "center",
+                },
+            ],
+            [div(
+                [style! {
+                    "width": "600px",
+                    "background": "#2b1d12",
+                    "border": "3px solid black",
+                    "padding": "20px",
+                    "font-family": "monospace",
+                    "color": "white",
+                }],
+                sheet,
+            )],
+        )
+    }
+
+    fn music(&self) -> Option<Track> {
+        Some(Track::Exam)
+    }
+
+    fn result(&self) -> Option<MiniGameResult> {
+        self.handed_in.then(|| MiniGameResult::WrittenExam { subject: self.subject, percent: self.percent(), passed: self.passed() })
+    }
+}
@@ -0,0 +1,137 @@
+//dosežki: silly goals that stay unlocked across runs (saved in localStorage), checked whenever something happens in the game
+
+use crate::models::affinity::MAX_AFFINITY;
+use crate::models::economy::TransactionKind;
+use crate::models::ending::{Ending, ALL_ENDINGS};
+use crate::models::gamestate::{DialogueNodes, GameState};
+use crate::models::interactable::NpcId;
+use crate::models::shop::ShopItem;
+use crate::models::storage;
+
+const STORAGE_KEY: &str = "fakslife_achievements";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AchievementId {
+    DesetKav,
+    EnCommit,
+    PrviIzpit,
+    Zaspanec,
+    Delavec,
+    NajboljšiPrijatelj,
+    Diplomant,
+    VsiKonci,
+}
+
+pub const ALL_ACHIEVEMENTS: [AchievementId; 8] = [
+    AchievementId::DesetKav,
+    AchievementId::EnCommit,
+    AchievementId::PrviIzpit,
+    AchievementId::Zaspanec,
+    AchievementId::Delavec,
+    AchievementId::NajboljšiPrijatelj,
+    AchievementId::Diplomant,
+    AchievementId::VsiKonci,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AchievementEvent {     //things that happen in the game that achievements listen to
+    Consumed(ShopItem),     //after it's already in the purchase history
+    ReachedNode(DialogueNodes),
+    ExamPassed,     //written or oral
+    WokeUp { minutes_late: u32 },
+    WorkedShift,
+    AffinityChanged(NpcId),
+    Ended(Ending),      //after the ending is already in the gallery
+}
+
+impl AchievementId {
+    fn id(&self) -> &'static str {      //what gets saved in localStorage, don't change these
+        match self {
+            AchievementId::DesetKav => "deset_kav",
+            AchievementId::EnCommit => "en_commit",
+            AchievementId::PrviIzpit => "prvi_izpit",
+            AchievementId::Zaspanec => "zaspanec",
+            AchievementId::Delavec => "delavec",
+            AchievementId::NajboljšiPrijatelj => "najboljsi_prijatelj",
+            AchievementId::Diplomant => "diplomant",
+            AchievementId::VsiKonci => "vsi_konci",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<AchievementId> {
+        ALL_ACHIEVEMENTS.iter().copied().find(|achievement| achievement.id() == id)
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            AchievementId::DesetKav => "Kofeinska zastrupitev",
+            AchievementId::EnCommit => "En commit",
+            AchievementId::PrviIzpit => "Prvi izpit",
+            AchievementId::Zaspanec => "Zaspanec",
+            AchievementId::Delavec => "Študentski servis",
+            AchievementId::NajboljšiPrijatelj => "Najboljša prijatelja",
+            AchievementId::Diplomant => "Diplomant",
+            AchievementId::VsiKonci => "[Videl|Videla] sem vse",      //titles go through GameState::say
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AchievementId::DesetKav => "Spij deset kav v eni igri.",
+            AchievementId::EnCommit => "Ema prizna, da je naredila samo en commit na README.",
+            AchievementId::PrviIzpit => "Opravi katerikoli izpit.",
+            AchievementId::Zaspanec => "Po prečuti noči zamudi več kot eno uro.",
+            AchievementId::Delavec => "Odpracuj pet izmen v eni igri.",
+            AchievementId::NajboljšiPrijatelj => "Naj te prijatelj ima rad čisto do konca.",
+            AchievementId::Diplomant => "Diplomiraj.",
+            AchievementId::VsiKonci => "Odkrij vse konce.",
+        }
+    }
+
+    //checked for every event, the game state is already updated when this runs
+    pub fn unlocked_by(&self, event: AchievementEvent, game_state: &GameState) -> bool {
+        match (self, event) {
+            (AchievementId::DesetKav, AchievementEvent::Consumed(ShopItem::Kava)) => {
+                game_state.purchases.iter().filter(|purchase| purchase.item == ShopItem::Kava).count() >= 10
+            }
+            (AchievementId::EnCommit, AchievementEvent::ReachedNode(node)) => node == DialogueNodes::EjToPaNiRes,
+            (AchievementId::PrviIzpit, AchievementEvent::ExamPassed) => true,
+            (AchievementId::Zaspanec, AchievementEvent::WokeUp { minutes_late }) => minutes_late > 60,
+            (AchievementId::Delavec, AchievementEvent::WorkedShift) => {
+                game_state.transactions.iter().filter(|transaction| transaction.kind == TransactionKind::Shift).count() >= 5
+            }
+            (AchievementId::NajboljšiPrijatelj, AchievementEvent::AffinityChanged(npc)) => {
+                npc == game_state.player.character.friend() && game_state.affinity.get(npc) >= MAX_AFFINITY
+            }
+            (AchievementId::Diplomant, AchievementEvent::Ended(ending)) => ending == Ending::Diploma,
+            (AchievementId::VsiKonci, AchievementEvent::Ended(_)) => ALL_ENDINGS.iter().all(|ending| game_state.endings.has(*ending)),
+            _ => false,
+        }
+    }
+}
+
+pub struct Achievements {
+    pub unlocked: Vec<AchievementId>,       //in the order they were unlocked
+}
+
+impl Achievements {
+    pub fn load() -> Self {     //same format as the endings: comma separated ids
+        let unlocked = storage::load(STORAGE_KEY)
+            .map(|saved| saved.split(',').filter_map(AchievementId::from_id).collect())
+            .unwrap_or_default();
+        Achievements { unlocked }
+    }
+
+    pub fn has(&self, achievement: AchievementId) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    pub fn unlock(&mut self, achievement: AchievementId) {
+        if self.has(achievement) {
+            return;
+        }
+        self.unlocked.push(achievement);
+        let ids: Vec<&str> = self.unlocked.iter().map(|achievement| achievement.id()).collect();
+        storage::save(STORAGE_KEY, &ids.join(","));
+    }
+}
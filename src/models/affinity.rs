@@ -0,0 +1,111 @@
+//odnosi z npc-ji: dialogue choices raise or lower how much someone likes you, that decides how they greet you and if they do you a favor
+
+use crate::models::interactable::NpcId;
+use std::collections::HashMap;
+
+pub const MIN_AFFINITY: i32 = -100;
+pub const MAX_AFFINITY: i32 = 100;
+pub const FRIENDLY: i32 = 30;       //from here on they're happy to see you and do you favors
+pub const UPSET: i32 = -30;     //at or below this they don't really want to talk to you
+
+pub struct Affinity {
+    values: HashMap<NpcId, i32>,        //everyone starts at 0
+    last_favor: HashMap<NpcId, u32>,        //day of the last favor, one favor per npc per day
+}
+
+impl Affinity {
+    pub fn new() -> Self {
+        Affinity { values: HashMap::new(), last_favor: HashMap::new() }
+    }
+
+    pub fn get(&self, npc: NpcId) -> i32 {
+        self.values.get(&npc).copied().unwrap_or(0)
+    }
+
+    pub fn change(&mut self, npc: NpcId, amount: i32) {
+        let value = self.values.entry(npc).or_insert(0);
+        *value = (*value + amount).clamp(MIN_AFFINITY, MAX_AFFINITY);
+    }
+
+    pub fn favor_ready(&self, npc: NpcId, day: u32) -> bool {
+        self.get(npc) >= FRIENDLY && self.last_favor.get(&npc) != Some(&day)
+    }
+
+    pub fn use_favor(&mut self, npc: NpcId, day: u32) {
+        self.last_favor.insert(npc, day);
+    }
+
+    pub fn last_favor(&self, npc: NpcId) -> Option<u32> {       //for the save file
+        self.last_favor.get(&npc).copied()
+    }
+
+    pub fn restore(&mut self, npc: NpcId, value: i32, last_favor: Option<u32>) {        //loading a saved game
+        self.values.insert(npc, value.clamp(MIN_AFFINITY, MAX_AFFINITY));
+        if let Some(day) = last_favor {
+            self.last_favor.insert(npc, day);
+        }
+    }
+
+    pub fn label(&self, npc: NpcId) -> &'static str {       //shown in the dialogue box
+        match self.get(npc) {
+            value if value >= FRIENDLY => "prijateljstvo",
+            value if value <= UPSET => "zamera",
+            _ => "nevtralno",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_follow_the_thresholds() {
+        let mut affinity = Affinity::new();
+        assert_eq!(affinity.label(NpcId::Ema), "nevtralno");
+        affinity.change(NpcId::Ema, FRIENDLY - 1);
+        assert_eq!(affinity.label(NpcId::Ema), "nevtralno");
+        affinity.change(NpcId::Ema, 1);
+        assert_eq!(affinity.label(NpcId::Ema), "prijateljstvo");
+        affinity.change(NpcId::Peteršič, UPSET);
+        assert_eq!(affinity.label(NpcId::Peteršič), "zamera");
+        affinity.change(NpcId::Peteršič, 1);
+        assert_eq!(affinity.label(NpcId::Peteršič), "nevtralno");
+    }
+
+    #[test]
+    fn affinity_is_clamped() {
+        let mut affinity = Affinity::new();
+        affinity.change(NpcId::Ema, 500);
+        assert_eq!(affinity.get(NpcId::Ema), MAX_AFFINITY);
+        affinity.change(NpcId::Ema, -1000);
+        assert_eq!(affinity.get(NpcId::Ema), MIN_AFFINITY);
+        assert_eq!(affinity.get(NpcId::Peteršič), 0);        //the others aren't touched
+    }
+
+    #[test]
+    fn one_favor_per_day() {
+        let mut affinity = Affinity::new();
+        affinity.change(NpcId::Peteršič, FRIENDLY - 1);
+        assert!(!affinity.favor_ready(NpcId::Peteršič, 1));
+        affinity.change(NpcId::Peteršič, 1);
+        assert!(affinity.favor_ready(NpcId::Peteršič, 1));
+        affinity.use_favor(NpcId::Peteršič, 1);
+        assert!(!affinity.favor_ready(NpcId::Peteršič, 1));
+        assert!(affinity.favor_ready(NpcId::Peteršič, 2));
+        affinity.change(NpcId::Ema, FRIENDLY);
+        assert!(affinity.favor_ready(NpcId::Ema, 1));       //favors are counted per npc
+    }
+
+    #[test]
+    fn restore_loads_a_saved_npc() {
+        let mut affinity = Affinity::new();
+        affinity.restore(NpcId::Ema, 250, Some(4));
+        assert_eq!(affinity.get(NpcId::Ema), MAX_AFFINITY);
+        assert_eq!(affinity.last_favor(NpcId::Ema), Some(4));
+        assert!(!affinity.favor_ready(NpcId::Ema, 4));
+        affinity.restore(NpcId::Peteršič, -40, None);
+        assert_eq!(affinity.last_favor(NpcId::Peteršič), None);
+        assert_eq!(affinity.label(NpcId::Peteršič), "zamera");
+    }
+}
@@ -0,0 +1,218 @@
+//glasba in zvočni efekti: two music channels that crossfade when the track changes, an anxiety layer on top of them,
+//and short one-shot effects made with WebAudio (we don't have any sound files for them)
+
+use crate::models::room::Room;
+use crate::models::settings::Settings;
+use web_sys::{AudioContext, HtmlAudioElement, OscillatorType};
+
+const CROSSFADE_TICKS: u32 = 90;        //about a second and a half
+const MAX_VOICES: usize = 6;        //effects playing at once, the rest are skipped so spamming a menu doesn't get loud
+const ANXIETY_STEM: Track = Track::Exam;       //the tense Orb theme goes on top of the calm music, not while it's already playing (exams)
+const ANXIETY_SMOOTHING: f64 = 0.02;        //how much of the way to the new anxiety level the music goes every tick
+const CALM_VOLUME: f64 = 0.6;       //music volume at 0 anxiety, it goes up to full at max anxiety
+const STEM_FROM: f64 = 0.5;     //the anxiety layer starts fading in at half the anxiety meter
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Track {
+    Main,
+    Exam,       //the Orb theme, see Story/Notes.tex
+}
+
+impl Track {
+    fn src(self) -> &'static str {
+        match self {
+            Track::Main => "/static/music/First-main.mp3",
+            Track::Exam => "/static/music/Kensuke UshioOrb_ On the Movements of the EarthOST - Main Theme (HQ Cover) チ-地球の運動について 牛尾憲輔.mp3",
+        }
+    }
+
+    pub fn for_room(room: Room) -> Track {
+        match room {
+            Room::Mafija | Room::DrugiŠtuk => Track::Main,
+            Room::Kabinet => Track::Exam,       //you only go there for oral exams
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sfx {
+    Purchase,
+    Door,
+    Dialogue,       //someone starts talking to you
+}
+
+impl Sfx {
+    fn wave(self) -> OscillatorType {
+        match self {
+            Sfx::Purchase => OscillatorType::Square,
+            Sfx::Door => OscillatorType::Triangle,
+            Sfx::Dialogue => OscillatorType::Sine,
+        }
+    }
+
+    fn notes(self) -> &'static [(f32, f64, f64)] {     //(pitch in Hz, start, length in seconds)
+        match self {
+            Sfx::Purchase => &[(988.0, 0.0, 0.08), (1319.0, 0.08, 0.2)],       //ka-ching
+            Sfx::Door => &[(90.0, 0.0, 0.15), (70.0, 0.06, 0.2)],       //a low thud
+            Sfx::Dialogue => &[(523.0, 0.0, 0.06), (784.0, 0.06, 0.08)],
+        }
+    }
+
+    fn volume(self) -> f32 {
+        match self {
+            Sfx::Purchase => 0.05,
+            Sfx::Door => 0.25,      //low notes sound quieter
+            Sfx::Dialogue => 0.1,
+        }
+    }
+}
+
+struct Channel {
+    track: Track,
+    element: HtmlAudioElement,
+    fade: f64,      //0-1, multiplied with the music volume setting
+}
+
+pub struct Audio {
+    channels: Vec<Channel>,     //the last one is the track that's playing, the others are fading out
+    stem: Option<HtmlAudioElement>,     //anxiety layer, only plays while you can hear it
+    stem_playing: bool,     //play() was called and we haven't paused it since, so it isn't called again every tick
+    stem_failed: bool,      //the file didn't load, we stop trying
+    anxiety: f64,       //0-1, follows the anxiety meter slowly so the music doesn't jump
+    context: Option<AudioContext>,      //for effects, made on the first one
+    voices: Vec<f64>,       //when the effects that are still playing end (in context time)
+    unlocked: bool,     //browsers only allow sound after you've pressed something
+}
+
+impl Audio {
+    pub fn new() -> Self {
+        Audio { channels: Vec::new(), stem: None, stem_playing: false, stem_failed: false, anxiety: 0.0, context: None, voices: Vec::new(), unlocked: false }
+    }
+
+    pub fn unlock(&mut self) {      //called on key presses and clicks, starts the music that was waiting
+        if self.unlocked {
+            return;
+        }
+        self.unlocked = true;
+        for channel in &self.channels {
+            let _ = channel.element.play();
+        }
+    }
+
+    //called every tick with the track that fits the current screen, a new track fades in over the old one
+    pub fn play_music(&mut self, track: Track) {
+        if self.channels.last().map(|channel| channel.track) == Some(track) {
+            return;
+        }
+        if let Some(index) = self.channels.iter().position(|channel| channel.track == track) {     //still fading out, it just fades back in from where it is
+            let channel = self.channels.remove(index);
+            self.channels.push(channel);
+            return;
+        }
+        let Ok(element) = HtmlAudioElement::new_with_src(track.src()) else {
+            return;
+        };
+        element.set_loop(true);
+        element.set_volume(0.0);
+        if self.unlocked {
+            let _ = element.play();
+        }
+        self.channels.push(Channel { track, element, fade: 0.0 });
+    }
+
+    //anxiety is the meter as 0-1 (0 outside of a run): the more anxious you are, the louder the music and the anxiety layer
+    pub fn tick(&mut self, settings: &Settings, anxiety: f64) {
+        self.anxiety += (anxiety.clamp(0.0, 1.0) - self.anxiety) * ANXIETY_SMOOTHING;
+        let music_volume = settings.music_volume as f64 / 100.0;
+        let loudness = CALM_VOLUME + (1.0 - CALM_VOLUME) * self.anxiety;
+        let current = self.channels.last().map(|channel| channel.track);
+        let stem = if current == Some(ANXIETY_STEM) { 0.0 } else { ((self.anxiety - STEM_FROM) / (1.0 - STEM_FROM)).clamp(0.0, 1.0) };
+        self.tick_stem(music_volume * stem);
+
+        let step = 1.0 / CROSSFADE_TICKS as f64;
+        for channel in &mut self.channels {
+            channel.fade = if Some(channel.track) == current {
+                (channel.fade + step).min(1.0)
+            } else {
+                (channel.fade - step).max(0.0)
+            };
+            channel.element.set_volume(channel.fade * music_volume * loudness);     //so the volume setting applies right away
+        }
+        self.channels.retain(|channel| {
+            let faded_out = Some(channel.track) != current && channel.fade <= 0.0;
+            if faded_out {
+                let _ = channel.element.pause();
+            }
+            !faded_out
+        });
+    }
+
+    fn tick_stem(&mut self, volume: f64) {
+        if self.stem_failed {
+            return;
+        }
+        if self.stem.is_none() && volume > 0.0 {
+            self.stem = HtmlAudioElement::new_with_src(ANXIETY_STEM.src()).ok();
+            if let Some(stem) = &self.stem {
+                stem.set_loop(true);
+            }
+        }
+        if self.stem.as_ref().is_some_and(|stem| stem.error().is_some()) {
+            self.stem = None;
+            self.stem_failed = true;
+            return;
+        }
+        let Some(stem) = &self.stem else {
+            return;
+        };
+        stem.set_volume(volume);
+        if volume > 0.0 && !self.stem_playing && self.unlocked {
+            self.stem_playing = true;
+            self.stem_failed = stem.play().is_err();
+        } else if volume <= 0.0 && self.stem_playing {
+            let _ = stem.pause();     //calmed down, next time it starts from the beginning again
+            stem.set_current_time(0.0);
+            self.stem_playing = false;
+        }
+    }
+
+    pub fn play(&mut self, sfx: Sfx, settings: &Settings) {
+        let wave = sfx.wave();
+        let gain = sfx.volume() * settings.sfx_gain();
+        for &(pitch, start, length) in sfx.notes() {
+            self.tone(wave, pitch, gain, start, length);
+        }
+    }
+
+    pub fn blip(&mut self, pitch: f32, settings: &Settings) {     //typewriter letters, a very short square wave
+        self.tone(OscillatorType::Square, pitch, 0.04 * settings.sfx_gain(), 0.0, 0.03);       //quiet, so it doesn't drown out the music
+    }
+
+    fn tone(&mut self, wave: OscillatorType, pitch: f32, volume: f32, start: f64, length: f64) {
+        if !self.unlocked || volume <= 0.0 {
+            return;
+        }
+        if self.context.is_none() {
+            self.context = AudioContext::new().ok();
+        }
+        let Some(context) = &self.context else {
+            return;
+        };
+        let now = context.current_time();
+        self.voices.retain(|&end| end > now);
+        if self.voices.len() >= MAX_VOICES {
+            return;
+        }
+        let (Ok(oscillator), Ok(gain)) = (context.create_oscillator(), context.create_gain()) else {
+            return;
+        };
+        oscillator.set_type(wave);
+        oscillator.frequency().set_value(pitch);
+        gain.gain().set_value(volume);
+        let _ = oscillator.connect_with_audio_node(&gain);
+        let _ = gain.connect_with_audio_node(&context.destination());
+        let _ = oscillator.start_with_when(now + start);
+        let _ = oscillator.stop_with_when(now + start + length);
+        self.voices.push(now + start + length);
+    }
+}
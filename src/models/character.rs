@@ -0,0 +1,193 @@
+//igralni liki: who you play as, everyone has their own look, starting meters and a couple of quirks
+
+use crate::models::interactable::NpcId;
+use crate::models::player::Smer;
+use crate::models::shop::ShopItem;
+
+pub const SMOKE_RELIEF: i32 = 15;       //how much a cigarette calms you down without any quirks
+pub const NERVES_MINUTES: u32 = 10;     //ŽivčenŽelodec: how long you're stuck on the WC before an exam
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Character {
+    Lan,
+    Ema,
+}
+
+pub const ALL_CHARACTERS: [Character; 2] = [Character::Lan, Character::Ema];        //in the order they're shown on the selection screen
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quirk {        //passive, they change what your actions do to you
+    Kofeinoman,     //kava gives more attention, but makes you a bit jumpier too
+    Kadilec,        //a cigarette calms you down more
+    ŽivčenŽelodec,      //before every exam you throw up first: it takes time and attention, but you're calmer after
+    Sladkosnedka,       //rogljiček calms you down twice as much
+}
+
+impl Quirk {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quirk::Kofeinoman => "Kofeinoman",
+            Quirk::Kadilec => "Kadilec",
+            Quirk::ŽivčenŽelodec => "Živčen želodec",
+            Quirk::Sladkosnedka => "Sladkosnedka",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {     //shown on the selection screen
+        match self {
+            Quirk::Kofeinoman => "Kava: pozornost +10, anksioznost +3.",
+            Quirk::Kadilec => "Cigareta te pomiri bolj (-25 namesto -15).",
+            Quirk::ŽivčenŽelodec => "Pred vsakim izpitom bruhaš: +10 min, pozornost -10, anksioznost -15.",
+            Quirk::Sladkosnedka => "Rogljiček te pomiri dvakrat bolj.",
+        }
+    }
+}
+
+impl Character {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Character::Lan => "Lan",
+            Character::Ema => "Ema",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Character::Lan => "Živi na kavi in cigaretah. Izpite piše na zadnji rok.",
+            Character::Ema => "Pridna in organizirana, ampak pred izpiti ji živci ne dajo miru.",
+        }
+    }
+
+    pub fn female(&self) -> bool {      //for the gendered words in dialogue and notifications (see say)
+        matches!(self, Character::Ema)
+    }
+
+    pub fn friend(&self) -> NpcId {     //the other one walks around Mafija as an npc
+        match self {
+            Character::Lan => NpcId::Ema,
+            Character::Ema => NpcId::Lan,
+        }
+    }
+
+    pub fn sprite(&self, smer: Smer) -> &'static str {
+        match (self, smer) {
+            (Character::Lan, Smer::Levo) => "/static/characters/lan_levo_4x.png",
+            (Character::Lan, Smer::Desno) => "/static/characters/lan_desno_4x.png",
+            (Character::Lan, Smer::Stoji) => "/static/characters/lan_naravnost_4x.png",
+            (Character::Ema, _) => "/static/characters/ema_naravnost.png",      //no side sprites for Ema yet
+        }
+    }
+
+    pub fn size(&self) -> (f64, f64) {      //og dimensions of the sprite (before scaling)
+        match self {
+            Character::Lan => (20.0, 62.0),
+            Character::Ema => (24.0, 62.0),
+        }
+    }
+
+    pub fn starting_money(&self) -> i32 {
+        match self {
+            Character::Lan => 100,
+            Character::Ema => 80,       //she already spent some on coffee for Lan
+        }
+    }
+
+    pub fn max_attention(&self) -> i32 {
+        match self {
+            Character::Lan => 100,
+            Character::Ema => 110,
+        }
+    }
+
+    pub fn starting_anxiety(&self) -> i32 {
+        match self {
+            Character::Lan => 0,
+            Character::Ema => 10,
+        }
+    }
+
+    pub fn quirks(&self) -> &'static [Quirk] {
+        match self {
+            Character::Lan => &[Quirk::Kofeinoman, Quirk::Kadilec],
+            Character::Ema => &[Quirk::ŽivčenŽelodec, Quirk::Sladkosnedka],
+        }
+    }
+
+    pub fn has(&self, quirk: Quirk) -> bool {
+        self.quirks().contains(&quirk)
+    }
+
+    //(anxiety, attention) change of something from the counter, after the quirks
+    pub fn item_effect(&self, item: ShopItem) -> (i32, i32) {
+        let mut anxiety = item.anxiety_change();
+        let mut attention = item.attention_change();
+        if item == ShopItem::Kava && self.has(Quirk::Kofeinoman) {
+            attention += 10;
+            anxiety += 3;
+        }
+        if item == ShopItem::Rogljiček && self.has(Quirk::Sladkosnedka) {
+            anxiety *= 2;
+        }
+        (anxiety, attention)
+    }
+
+    pub fn smoke_relief(&self) -> i32 {
+        if self.has(Quirk::Kadilec) { SMOKE_RELIEF + 10 } else { SMOKE_RELIEF }
+    }
+
+    //fills in the name ({ime}) and picks the right form of gendered words ([šel|šla]) in dialogue and notifications
+    pub fn say(&self, text: &str) -> String {
+        let text = text.replace("{ime}", self.name());
+        let mut said = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find('[') {
+            let Some(length) = rest[start..].find(']') else {
+                break;
+            };
+            let end = start + length;
+            match rest[start + 1..end].split_once('|') {
+                Some((male, female)) => {
+                    said.push_str(&rest[..start]);
+                    said.push_str(if self.female() { female } else { male });
+                }
+                None => said.push_str(&rest[..=end]),      //just a normal bracket, leave it alone
+            }
+            rest = &rest[end + 1..];
+        }
+        said.push_str(rest);
+        said
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn say_fills_in_the_name_and_gender() {
+        assert_eq!(Character::Lan.say("{ime}, si [šel|šla] na kavo?"), "Lan, si šel na kavo?");
+        assert_eq!(Character::Ema.say("{ime}, si [šel|šla] na kavo?"), "Ema, si šla na kavo?");
+        assert_eq!(Character::Ema.say("[Rad|Rada] bi [prišel|prišla]."), "Rada bi prišla.");
+    }
+
+    #[test]
+    fn say_leaves_other_brackets_alone() {
+        assert_eq!(Character::Ema.say("P(A) [glej zapiske] in [šel|šla]"), "P(A) [glej zapiske] in šla");
+        assert_eq!(Character::Ema.say("odprt [oklepaj|brez konca"), "odprt [oklepaj|brez konca");
+        assert_eq!(Character::Lan.say("] [] [|]"), "] [] ");
+    }
+
+    #[test]
+    fn quirks_change_item_effects() {
+        assert_eq!(Character::Lan.item_effect(ShopItem::Kava), (ShopItem::Kava.anxiety_change() + 3, ShopItem::Kava.attention_change() + 10));
+        assert_eq!(Character::Ema.item_effect(ShopItem::Kava), (ShopItem::Kava.anxiety_change(), ShopItem::Kava.attention_change()));
+        assert_eq!(Character::Ema.item_effect(ShopItem::Rogljiček).0, ShopItem::Rogljiček.anxiety_change() * 2);
+        assert_eq!(Character::Lan.item_effect(ShopItem::Tortilja), (ShopItem::Tortilja.anxiety_change(), 0));
+    }
+
+    #[test]
+    fn smokers_calm_down_more() {
+        assert_eq!(Character::Lan.smoke_relief(), SMOKE_RELIEF + 10);
+        assert_eq!(Character::Ema.smoke_relief(), SMOKE_RELIEF);
+    }
+}
@@ -0,0 +1,54 @@
+//in-game clock, everything that depends on the time of day (npc schedules etc.) reads from here
+
+pub const TICKS_PER_MINUTE: u32 = 15;      //one tick is 16 ms so one in-game minute lasts ~0.25 s and an hour ~15 s
+pub const DAY_START: u32 = 8 * 60;      //every day starts at 8:00 (in minutes after midnight)
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+pub struct GameClock {
+    pub day: u32,       //which day we're on, starting with 1
+    pub minutes: u32,       //minutes after midnight (8:30 = 510)
+    ticks: u32,     //ticks since the last in-game minute
+}
+
+impl GameClock {
+    pub fn new() -> Self {
+        GameClock {
+            day: 1,
+            minutes: DAY_START,
+            ticks: 0,
+        }
+    }
+
+    //called on every Tick, returns true when a new in-game minute starts
+    pub fn advance(&mut self) -> bool {
+        self.ticks += 1;
+        if self.ticks < TICKS_PER_MINUTE {
+            return false;
+        }
+        self.ticks = 0;
+        self.advance_minutes(1);
+        true
+    }
+
+    //skipping time (ex. when you sit in a lecture), midnight rolls over into the next morning
+    pub fn advance_minutes(&mut self, amount: u32) {
+        self.minutes += amount;
+        while self.minutes >= MINUTES_PER_DAY {
+            self.minutes -= MINUTES_PER_DAY;
+            self.day += 1;
+            self.minutes = self.minutes.max(DAY_START);     //nobody is awake between midnight and 8:00 :)
+        }
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.minutes / 60
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minutes % 60
+    }
+
+    pub fn label(&self) -> String {     //what we show in the HUD
+        format!("Dan {}, {:02}:{:02}", self.day, self.hour(), self.minute())
+    }
+}
@@ -0,0 +1,45 @@
+//where money comes from and where it goes, every change of player.money is recorded as a transaction
+
+use crate::models::shop::ShopItem;
+
+pub const WEEKLY_ALLOWANCE: i32 = 40;       //žepnina from home
+pub const ALLOWANCE_EVERY_DAYS: u32 = 7;
+
+//student job at the Mafija counter
+pub const SHIFT_PAY: i32 = 15;
+pub const SHIFT_MINUTES: u32 = 2 * 60;
+pub const SHIFT_ATTENTION_COST: i32 = 25;
+pub const SHIFT_ANXIETY_COST: i32 = 5;
+pub const LAST_SHIFT_START: u32 = 18 * 60;      //Mafija closes at 20:00 so the last shift starts at 18:00
+
+pub const BOOK_RESALE_PRICE: i32 = 4;       //antikvariat doesn't pay much
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    Purchase(ShopItem),
+    Allowance,
+    Shift,
+    BookSold,
+    Mishap,     //lost (or found) money in a nevšečnost
+    QuestReward,
+}
+
+impl TransactionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransactionKind::Purchase(item) => item.name(),
+            TransactionKind::Allowance => "Žepnina",
+            TransactionKind::Shift => "Izmena v Mafiji",
+            TransactionKind::BookSold => "Prodana knjiga",
+            TransactionKind::Mishap => "Nevšečnost",
+            TransactionKind::QuestReward => "Nagrada za nalogo",
+        }
+    }
+}
+
+pub struct Transaction {
+    pub day: u32,
+    pub minutes: u32,
+    pub amount: i32,        //positive = earned, negative = spent
+    pub kind: TransactionKind,
+}
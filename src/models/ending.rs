@@ -0,0 +1,89 @@
+//konci: every way a run can end, and the gallery of the ones you've already found (remembered across runs)
+
+use crate::models::storage;
+
+const STORAGE_KEY: &str = "fakslife_endings";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    PrevečKave,     //anxiety maxed out right after a coffee
+    PaničniNapad,       //anxiety maxed out any other way
+    Rešilec,        //Ema calls an ambulance because you "lost your memory"
+    Domov,      //you gave up and went home
+    Diploma,        //you finished the last year
+}
+
+pub const ALL_ENDINGS: [Ending; 5] = [Ending::PrevečKave, Ending::PaničniNapad, Ending::Rešilec, Ending::Domov, Ending::Diploma];        //in the order they're shown in the gallery
+
+impl Ending {
+    fn id(&self) -> &'static str {      //what gets saved in localStorage, don't change these or old saves forget the ending
+        match self {
+            Ending::PrevečKave => "kava",
+            Ending::PaničniNapad => "panika",
+            Ending::Rešilec => "resilec",
+            Ending::Domov => "domov",
+            Ending::Diploma => "diploma",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Ending> {
+        ALL_ENDINGS.iter().copied().find(|ending| ending.id() == id)
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Ending::PrevečKave => "Preveč kave",
+            Ending::PaničniNapad => "Panični napad",
+            Ending::Rešilec => "Rešilec",
+            Ending::Domov => "Domov",
+            Ending::Diploma => "Diploma",
+        }
+    }
+
+    pub fn text(&self) -> &'static str {        //goes through GameState::say, so it can use [šel|šla]
+        match self {
+            Ending::PrevečKave => "Še ena kava je bila ena preveč. Srce ti razbija, roke se tresejo in iz Mafije te odnesejo na nosilih.",
+            Ending::PaničniNapad => "Vsega je bilo preveč: izpiti, roki, projektna... Sredi faksa te zagrabi panika in ta semester je zate končan.",
+            Ending::Rešilec => "Ema je res poklicala rešilca. Zdravniki ne najdejo nič, ampak izpitnega roka si vseeno [zamudil|zamudila].",
+            Ending::Domov => "[Šel|Šla] si domov in se ne vrneš več. Mogoče pa faks vseeno ni zate.",
+            Ending::Diploma => "Vsi izpiti so opravljeni. Diploma je tvoja!",
+        }
+    }
+
+    pub fn art(&self) -> &'static str {     //background of the game over screen
+        match self {
+            Ending::PrevečKave => "/static/background/Kavarna.png",
+            Ending::PaničniNapad | Ending::Rešilec => "/static/background/game_over_screen.png",
+            Ending::Domov => "/static/background/start/sunset_start.png",
+            Ending::Diploma => "/static/background/start/classroom_pixelart.png",
+        }
+    }
+}
+
+pub struct EndingsGallery {
+    pub discovered: Vec<Ending>,        //in the order they were found
+}
+
+impl EndingsGallery {
+    pub fn load() -> Self {     //saved as a comma separated list of ids, unknown ids are skipped
+        let discovered = storage::load(STORAGE_KEY)
+            .map(|saved| saved.split(',').filter_map(Ending::from_id).collect())
+            .unwrap_or_default();
+        EndingsGallery { discovered }
+    }
+
+    pub fn has(&self, ending: Ending) -> bool {
+        self.discovered.contains(&ending)
+    }
+
+    //returns whether the ending is new, new ones get saved right away
+    pub fn discover(&mut self, ending: Ending) -> bool {
+        if self.has(ending) {
+            return false;
+        }
+        self.discovered.push(ending);
+        let ids: Vec<&str> = self.discovered.iter().map(|ending| ending.id()).collect();
+        storage::save(STORAGE_KEY, &ids.join(","));
+        true
+    }
+}
@@ -0,0 +1,307 @@
+//written exams: question banks per subject (from data/exams) and the state of an exam that's being written
+
+use crate::models::rng::Rng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subject {
+    Analiza1,
+    Fizika,
+    Programiranje2,
+    Stop,
+    PodatkovneBaze,
+}
+
+pub const ALL_SUBJECTS: [Subject; 5] = [Subject::Analiza1, Subject::Fizika, Subject::Programiranje2, Subject::Stop, Subject::PodatkovneBaze];     //in the order of the study programme
+
+impl Subject {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subject::Analiza1 => "Analiza 1",
+            Subject::Fizika => "Fizika",
+            Subject::Programiranje2 => "Programiranje 2",
+            Subject::Stop => "STOP",
+            Subject::PodatkovneBaze => "Podatkovne baze",
+        }
+    }
+
+    pub fn year(&self) -> u32 {     //v katerem letniku je predmet
+        match self {
+            Subject::Analiza1 | Subject::Fizika => 1,
+            Subject::Programiranje2 | Subject::Stop => 2,
+            Subject::PodatkovneBaze => 3,
+        }
+    }
+
+    pub fn ects(&self) -> u32 {
+        match self {
+            Subject::Analiza1 => 10,
+            Subject::Fizika | Subject::Programiranje2 | Subject::Stop | Subject::PodatkovneBaze => 6,
+        }
+    }
+
+    pub fn has_oral(&self) -> bool {       //subjects with a professor in the kabinet also need an oral exam after the written one
+        matches!(self, Subject::Fizika | Subject::Stop)
+    }
+
+    fn question_bank(&self) -> &'static str {       //the files get baked into the wasm, so writers only have to edit the txt files
+        match self {
+            Subject::Analiza1 => include_str!("../../data/exams/analiza.txt"),
+            Subject::Fizika => include_str!("../../data/exams/fizika.txt"),
+            Subject::Programiranje2 => include_str!("../../data/exams/prog2.txt"),
+            Subject::Stop => include_str!("../../data/exams/stop.txt"),
+            Subject::PodatkovneBaze => include_str!("../../data/exams/baze.txt"),
+        }
+    }
+
+    pub fn questions(&self) -> Vec<Question> {
+        parse_questions(self.question_bank())
+    }
+}
+
+pub struct Question {
+    pub text: String,
+    pub answers: Vec<String>,
+    pub correct: usize,     //index into answers
+}
+
+//format is described in data/exams/README.txt, broken questions (not exactly one correct answer, too few answers) are skipped with a warning
+pub fn parse_questions(source: &str) -> Vec<Question> {
+    let mut questions = vec![];
+    let mut text: Option<String> = None;
+    let mut answers: Vec<String> = vec![];
+    let mut correct: Vec<usize> = vec![];       //indexes of the * answers, there should be exactly one
+
+    let mut flush = |text: &mut Option<String>, answers: &mut Vec<String>, correct: &mut Vec<usize>| {
+        if let Some(t) = text.take() {
+            if correct.len() != 1 {
+                log::warn!("exam question skipped, it has {} correct answers instead of one: {}", correct.len(), t);
+            } else if !(2..=4).contains(&answers.len()) {
+                log::warn!("exam question skipped, it has {} answers instead of 2-4: {}", answers.len(), t);
+            } else {
+                questions.push(Question { text: t, answers: std::mem::take(answers), correct: correct[0] });
+            }
+        }
+        answers.clear();
+        correct.clear();
+    };
+
+    for line in source.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix('?') {
+            flush(&mut text, &mut answers, &mut correct);
+            text = Some(rest.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix('*') {
+            correct.push(answers.len());
+            answers.push(rest.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix('-') {
+            answers.push(rest.trim().to_string());
+        }
+        //empty lines and # comments are ignored
+    }
+    flush(&mut text, &mut answers, &mut correct);
+
+    questions
+}
+
+pub const QUESTIONS_PER_EXAM: usize = 5;        //pisni izpiti so kratki
+pub const BASE_SECONDS_PER_QUESTION: u32 = 20;
+pub const TICKS_PER_SECOND: u32 = 60;       //one tick is ~16 ms
+
+pub struct ShownAnswer {        //an answer the way the player sees it
+    pub text: String,       //maybe scrambled or hidden
+    pub index: usize,       //which answer of the question it really is
+}
+
+pub struct WrittenExam {
+    pub subject: Subject,
+    pub questions: Vec<Question>,
+    pub current: usize,     //index of the question we're on
+    pub shown: Vec<ShownAnswer>,        //answers of the current question, shuffled
+    pub selection: usize,       //index into shown
+    pub correct_count: usize,
+    pub ticks_left: u32,
+    pub ticks_per_question: u32,
+    pub finished: bool,
+    pub handed_in: bool,        //result was shown and you pressed Enter, back to the game
+    scramble_chance: f64,       //low attention: letters in answers get mixed up
+    hidden_count: usize,        //high anxiety: some answers turn into ???
+}
+
+impl WrittenExam {
+    pub fn new(subject: Subject) -> Self {      //empty sheet, the questions are handed out in start()
+        WrittenExam {
+            subject,
+            questions: vec![],
+            current: 0,
+            shown: vec![],
+            selection: 0,
+            correct_count: 0,
+            ticks_left: 0,
+            ticks_per_question: 0,
+            finished: false,
+            handed_in: false,
+            scramble_chance: 0.0,
+            hidden_count: 0,
+        }
+    }
+
+    //difficulty is decided once at the start from the meters, the exam itself is the same for everyone
+    pub fn start(&mut self, attention: i32, max_attention: i32, anxiety: i32, max_anxiety: i32, rng: &mut Rng) {
+        let mut questions = self.subject.questions();
+        rng.shuffle(&mut questions);
+        questions.truncate(QUESTIONS_PER_EXAM);
+
+        let attention_ratio = attention as f64 / max_attention as f64;
+        let anxiety_ratio = anxiety as f64 / max_anxiety as f64;
+
+        let scramble_chance = ((0.5 - attention_ratio) * 2.0).clamp(0.0, 1.0);     //below half attention it starts getting blurry
+        let hidden_count = if anxiety_ratio >= 0.85 {
+            2
+        } else if anxiety_ratio >= 0.6 {
+            1
+        } else {
+            0
+        };
+        let seconds = BASE_SECONDS_PER_QUESTION - (anxiety_ratio * 10.0) as u32;        //anxiety eats up to 10 seconds per question
+
+        self.questions = questions;
+        self.current = 0;
+        self.correct_count = 0;
+        self.ticks_per_question = seconds * TICKS_PER_SECOND;
+        self.scramble_chance = scramble_chance;
+        self.hidden_count = hidden_count;
+        self.prepare_question(rng);
+    }
+
+    fn prepare_question(&mut self, rng: &mut Rng) {
+        self.selection = 0;
+        self.ticks_left = self.ticks_per_question;
+        let Some(question) = self.questions.get(self.current) else {
+            self.finished = true;
+            self.shown.clear();
+            return;
+        };
+
+        let mut shown: Vec<ShownAnswer> = question
+            .answers
+            .iter()
+            .enumerate()
+            .map(|(index, answer)| ShownAnswer {
+                text: if rng.chance(self.scramble_chance) { scramble(answer, rng) } else { answer.clone() },
+                index,
+            })
+            .collect();
+        rng.shuffle(&mut shown);
+
+        let mut hide_order: Vec<usize> = (0..shown.len()).collect();
+        rng.shuffle(&mut hide_order);
+        for &i in hide_order.iter().take(self.hidden_count.min(shown.len() - 1)) {     //at least one answer stays readable
+            shown[i].text = "???".to_string();
+        }
+
+        self.shown = shown;
+    }
+
+    pub fn question(&self) -> Option<&Question> {
+        self.questions.get(self.current)
+    }
+
+    pub fn answer(&mut self, rng: &mut Rng) {       //confirms the selected answer and moves to the next question
+        if self.finished {
+            return;
+        }
+        let correct = self.question().map(|q| q.correct);
+        if self.shown.get(self.selection).map(|a| a.index) == correct {
+            self.correct_count += 1;
+        }
+        self.current += 1;
+        self.prepare_question(rng);
+    }
+
+    pub fn tick(&mut self, rng: &mut Rng) {     //when time runs out the question counts as wrong
+        if self.finished {
+            return;
+        }
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        if self.ticks_left == 0 {
+            self.current += 1;
+            self.prepare_question(rng);
+        }
+    }
+
+    pub fn hand_in(&mut self) {     //only once the result is on screen
+        if self.finished {
+            self.handed_in = true;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selection = self.selection.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selection + 1 < self.shown.len() {
+            self.selection += 1;
+        }
+    }
+
+    pub fn percent(&self) -> u32 {
+        if self.questions.is_empty() {
+            return 0;
+        }
+        (self.correct_count * 100 / self.questions.len()) as u32
+    }
+
+    pub fn passed(&self) -> bool {
+        self.percent() >= 50
+    }
+}
+
+//mixes up the letters inside every longer word, first and last letter stay so it's still kind of readable
+fn scramble(text: &str, rng: &mut Rng) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars: Vec<char> = word.chars().collect();
+            if chars.len() > 3 {
+                let last = chars.len() - 1;
+                rng.shuffle(&mut chars[1..last]);
+            }
+            chars.into_iter().collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_questions() {
+        let questions = parse_questions("# komentar\n? Koliko je 1 + 1?\n- 1\n* 2\n- 3\n\n? Ali je nebo modro?\n* ja\n- ne\n");
+        assert_eq!(questions.len(), 2);
+        assert_eq!(questions[0].text, "Koliko je 1 + 1?");
+        assert_eq!(questions[0].answers, vec!["1", "2", "3"]);
+        assert_eq!(questions[0].correct, 1);
+        assert_eq!(questions[1].correct, 0);
+    }
+
+    #[test]
+    fn skips_questions_without_exactly_one_correct_answer() {
+        let questions = parse_questions("? dva pravilna\n* a\n* b\n- c\n\n? brez pravilnega\n- a\n- b\n\n? ok\n- a\n* b\n");
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].text, "ok");
+    }
+
+    #[test]
+    fn skips_questions_with_too_few_or_too_many_answers() {
+        let questions = parse_questions("? samo en\n* a\n\n? pet\n* a\n- b\n- c\n- d\n- e\n");
+        assert!(questions.is_empty());
+    }
+
+    #[test]
+    fn every_question_bank_has_enough_questions() {
+        for subject in ALL_SUBJECTS {
+            assert!(subject.questions().len() >= QUESTIONS_PER_EXAM, "{:?}", subject);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//kontroler: the browser Gamepad API is read every tick and turned into the same actions as the keyboard
+
+use crate::models::input::{Action, ALL_ACTIONS};
+use std::collections::HashSet;
+use wasm_bindgen::JsCast;
+use web_sys::{window, Gamepad, GamepadButton};
+
+pub const DEADZONE: f64 = 0.25;     //sticks never rest exactly at 0, everything below this counts as not touched
+pub const STICK_PRESS: f64 = 0.6;       //how far the stick has to go to count as a press (menus, dialogue)
+
+//buttons in the browser's "standard" layout, named like on an Xbox controller
+const BUTTON_ACTIONS: [(u32, Action); 10] = [
+    (0, Action::Confirm),       //A
+    (1, Action::Cancel),        //B
+    (2, Action::Interact),      //X
+    (3, Action::Inventory),     //Y
+    (4, Action::Transcript),        //LB
+    (5, Action::QuestLog),      //RB
+    (12, Action::MoveUp),       //d-pad
+    (13, Action::MoveDown),
+    (14, Action::MoveLeft),
+    (15, Action::MoveRight),
+];
+
+pub struct GamepadInput {
+    pub stick: (f64, f64),      //left stick after the deadzone, both axes from -1 to 1 (y goes down like the screen)
+    held: HashSet<Action>,      //buttons and the stick pushed far enough, this tick
+    previous: HashSet<Action>,      //same for the last tick, so we know what was only just pressed
+}
+
+impl GamepadInput {
+    pub fn new() -> Self {
+        GamepadInput { stick: (0.0, 0.0), held: HashSet::new(), previous: HashSet::new() }
+    }
+
+    //reads every connected controller, returns the actions that were pressed this tick (holding a button doesn't repeat it)
+    pub fn poll(&mut self) -> Vec<Action> {
+        self.previous = std::mem::take(&mut self.held);
+        self.stick = (0.0, 0.0);
+
+        for gamepad in connected() {
+            let axes = gamepad.axes();
+            let x = deadzone(axes.get(0).as_f64().unwrap_or(0.0));
+            let y = deadzone(axes.get(1).as_f64().unwrap_or(0.0));
+            if x != 0.0 || y != 0.0 {
+                self.stick = (x, y);        //if there's more than one controller, the last one that's touched wins
+            }
+
+            let buttons = gamepad.buttons();
+            for (index, action) in BUTTON_ACTIONS {
+                let pressed = buttons.get(index).dyn_into::<GamepadButton>().is_ok_and(|button| button.pressed());
+                if pressed {
+                    self.held.insert(action);
+                }
+            }
+        }
+
+        let (x, y) = self.stick;
+        for (pushed, action) in [(y < -STICK_PRESS, Action::MoveUp), (y > STICK_PRESS, Action::MoveDown), (x < -STICK_PRESS, Action::MoveLeft), (x > STICK_PRESS, Action::MoveRight)] {
+            if pushed {
+                self.held.insert(action);
+            }
+        }
+
+        ALL_ACTIONS
+            .iter()
+            .copied()
+            .filter(|action| self.held.contains(action) && !self.previous.contains(action))
+            .collect()
+    }
+
+    pub fn held(&self, action: Action) -> bool {
+        self.held.contains(&action)
+    }
+}
+
+fn connected() -> Vec<Gamepad> {        //empty slots in the list are null, dyn_into skips them
+    let Some(gamepads) = window().and_then(|window| window.navigator().get_gamepads().ok()) else {
+        return vec![];
+    };
+    gamepads.iter().filter_map(|gamepad| gamepad.dyn_into::<Gamepad>().ok()).collect()
+}
+
+pub fn deadzone(value: f64) -> f64 {       //0 inside the deadzone, outside it's scaled so it still goes smoothly up to 1
+    if value.abs() < DEADZONE {
+        return 0.0;
+    }
+    value.signum() * (value.abs() - DEADZONE) / (1.0 - DEADZONE)
+}
@@ -2,6 +2,32 @@
 
 use crate::models::player::Player;
 use crate::models::interactable::{Interactable, NpcId, Objects};
+use crate::models::clock::GameClock;
+use crate::models::room::Room;
+use crate::models::npc::Npc;
+use crate::models::pathfinding::{find_path, overlaps_any};
+use crate::models::inventory::ItemKind;
+use crate::models::shop::{ShopItem, Payment, Purchase, COUNTER_ITEMS, DAILY_VOUCHERS};
+use crate::models::economy::{self, Transaction, TransactionKind};
+use crate::models::exam::{Subject, WrittenExam};
+use crate::minigames::{MiniGame, MiniGameResult};
+use crate::models::transcript::{self, ExamAttempt, Transcript};
+use crate::models::oral_exam::{self, OralExam};
+use crate::models::mishap::{self, ActiveMishap, Mishap, MishapEffect, MishapRecord, Trigger};
+use crate::models::wake_up::{self, WakeUp};
+use crate::models::affinity::{self, Affinity};
+use crate::models::quest::{Objective, QuestEvent, QuestId, QuestLog, QuestStatus, Reward};
+use crate::models::character::{Character, Quirk, NERVES_MINUTES};
+use crate::models::ending::{Ending, EndingsGallery};
+use crate::models::achievement::{AchievementEvent, Achievements, ALL_ACHIEVEMENTS};
+use crate::models::input::{Action, KeyBindings};
+use crate::models::gamepad::GamepadInput;
+use crate::models::rng::Rng;
+use crate::models::touch::TouchInput;
+use crate::models::typewriter::Typewriter;
+use crate::models::audio::{Audio, Sfx, Track};
+use crate::models::save;
+use crate::models::settings::{Language, Settings, SettingsRow};
 use std::collections::HashSet;     //used to store pressed keys
 use std::collections::HashMap;     //used to store dialogue nodes
 use web_sys::window;    //so we can get the screen size
@@ -9,10 +35,26 @@ use web_sys::window;    //so we can get the screen size
 
 pub enum Screen {      //defines which part/screen of your game you're on
     Start,
-    StartPressed,   //temporary state after start button is clicked
+    CharacterSelect { selection: Character },        //after Start: pick who you play as (selection is for the keyboard/controller)
+    StartPressed,   //temporary state after a character is picked
     Playing,
+    Paused {        //pause menu on top of the frozen game (Esc)
+        selection: PauseOption,
+    },
     //MainMenu,
-    GameOver,
+    MiniGame(Box<dyn MiniGame>),        //pisni izpit, getting out of bed... the mini-game draws the whole screen
+    GameOver(Ending),       //which ending decides the title, text and picture
+    Graduated,      //win: you finished the last year
+    Endings,        //gallery of endings found so far (from the start screen)
+    Achievements,       //list of dosežki, locked ones greyed out (from the start screen)
+    Settings {      //nastavitve (from the start screen or the pause menu)
+        selection: SettingsRow,
+        from_pause: bool,       //"back" goes to the pause menu instead of the start screen
+    },
+    KeyBindings {       //remapping screen (from the settings)
+        waiting: Option<(Action, usize)>,       //the slot that gets the next key you press
+        from_pause: bool,       //passed on to the settings screen when you go back
+    },
 }
 
 pub struct Wall {
@@ -22,6 +64,7 @@ pub struct Wall {
     pub height: f64,
 }
 
+#[derive(Clone)]
 pub struct Item {       //interactive items
     pub kind: Interactable,
     pub x: f64,
@@ -40,28 +83,71 @@ pub enum InteractionState {     //enum for interactive items
     Dialogue{
         npc: NpcId,
         node: DialogueNodes,
+        exam: Option<OralExam>,     //Some while the dialogue is an oral exam (keeps the score)
+        selection: usize,       //index of the highlighted response (among the ones that are shown)
     },
+    Inventory{
+        selection: usize,       //index of the selected slot in player.inventory.slots
+    },
+    Transcript,     //indeks: grades, ECTS and attempts (T)
+    QuestLog,       //naloge (Q)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MenuOption {       //all possible menu options we have
-    Coffee,
-    Tortilla,
+    Buy(ShopItem),      //pay with money
+    BuyWithVoucher(ShopItem),       //pay with a študentski bon
+    WorkShift,      //študentsko delo za pultom
+    SellBook,
     Smoke,
     GoHome,
+    GoToRoom(Room),
+    WriteExam(Subject),
 }
 
 impl MenuOption {
-    pub fn label(&self) -> &'static str {    //labels for menu options we want to show on screen
+    pub fn label(&self) -> String {    //labels for menu options we want to show on screen
+        match self {
+            MenuOption::Buy(item) => format!("Buy {} ({} €)", item.name().to_lowercase(), item.price()),
+            MenuOption::BuyWithVoucher(item) => format!("Buy {} (1 bon)", item.name().to_lowercase()),
+            MenuOption::WorkShift => format!("Work a shift (+{} €, {} h)", economy::SHIFT_PAY, economy::SHIFT_MINUTES / 60),
+            MenuOption::SellBook => format!("Sell a book (+{} €)", economy::BOOK_RESALE_PRICE),
+            MenuOption::Smoke => "Smoke".to_string(),
+            MenuOption::GoHome => "Go home".to_string(),
+            MenuOption::GoToRoom(room) => format!("Go to {}", room.name()),
+            MenuOption::WriteExam(subject) => format!("Write exam: {}", subject.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseOption {
+    Resume,
+    Settings,
+    Save,
+    Load,
+    Quit,       //back to the start screen, whatever isn't saved is lost
+}
+
+pub const PAUSE_OPTIONS: [PauseOption; 5] = [PauseOption::Resume, PauseOption::Settings, PauseOption::Save, PauseOption::Load, PauseOption::Quit];
+
+impl PauseOption {
+    pub fn label(&self, language: Language) -> &'static str {
         match self {
-            MenuOption::Coffee => "Buy coffee",
-            MenuOption::Tortilla => "Buy tortilla",
-            MenuOption::Smoke => "Smoke",
-            MenuOption::GoHome => "Go home",
+            PauseOption::Resume => language.pick("Nadaljuj", "Resume"),
+            PauseOption::Settings => language.pick("Nastavitve", "Settings"),
+            PauseOption::Save => language.pick("Shrani igro", "Save game"),
+            PauseOption::Load => language.pick("Naloži igro", "Load game"),
+            PauseOption::Quit => language.pick("Na začetni zaslon", "Quit to title"),
         }
     }
 }
 
+pub struct Notification {       //short message at the top of the screen (ex. "you don't have enough money")
+    pub text: String,
+    pub ticks_left: u32,        //disappears when this reaches 0
+}
+
 
 pub struct DialogueNode {       
     pub text: &'static str,     //what the npc says at current node
@@ -71,6 +157,41 @@ pub struct DialogueNode {
 pub struct DialogueResponse {
     pub text: &'static str,     //what answer we choose (AKA which edge we choose)
     pub outcome: DialogueOutcome,       //what's the outcome after we choose that answer (AKA to which node we move next)
+    pub condition: Option<Condition>,       //if set, the response only shows up when the condition holds
+    pub effects: Vec<DialogueEffect>,       //what else happens when we choose it
+    pub score: i32,     //points for the oral exam track (0 outside of exams)
+}
+
+impl Default for DialogueResponse {     //so responses only have to mention the fields they actually use
+    fn default() -> Self {
+        DialogueResponse {
+            text: "",
+            outcome: DialogueOutcome::EndDialogue,
+            condition: None,
+            effects: vec![],
+            score: 0,
+        }
+    }
+}
+
+pub enum Condition {        //checks against the player's inventory and meters (dialogue responses and mishaps)
+    Has(ItemKind),
+    Missing(ItemKind),
+    AnxietyBelow(i32),
+    AnxietyAtLeast(i32),
+    AttentionAtLeast(i32),
+    AttentionBelow(i32),
+    ReadyForOral(Subject),      //written part passed, oral not yet, and it's this year's subject
+    NotReadyForOral(Subject),
+    FavorReady(NpcId),      //likes you enough and hasn't done you a favor today yet
+}
+
+pub enum DialogueEffect {
+    GiveItem(ItemKind),     //npc gives the player an item
+    TakeItem(ItemKind),     //npc takes an item from the player
+    Affinity(i32),      //the npc likes you more (or less)
+    Treat(ShopItem),        //the npc pays for something at the counter, that's their favor for the day
+    StartQuest(QuestId),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -99,12 +220,61 @@ pub enum DialogueNodes {     //start of every npc line
     AhSiMeŽerestrašil,
     OkSeVidiva,
     OhToRavno,
+    NašlaSemList,
+    EmaVesela,      //greeting when Ema likes you
+    EmaČastim,
+    EmaJezna,       //greeting when she's upset with you
+    EmaOdpustila,
+
+    //Lan, when you play as Ema
+    LanŽivjo,
+    LanKava,
+    LanReadme,
+    LanJutri,
+    LanKdoSi,
+    LanVesel,       //greeting when Lan likes you
+    LanČasti,
+    LanJezen,       //greeting when he's upset with you
+    LanPozabljeno,
+
+    //ustni izpit pri Peteršiču (STOP)
+    PeteršičZačetek,
+    PeteršičNajprejPisni,
+    PeteršičPogojna,
+    PeteršičKockeDa,
+    PeteršičKockeNe,
+    PeteršičBinomska,
+    PeteršičUgibDa,
+    PeteršičUgibNe,
+    PeteršičNeodvisnost,
+    PeteršičKonec,
+
+    //ustni izpit pri Različniku (fizika)
+    RazličnikZačetek,
+    RazličnikNajprejPisni,
+    RazličnikKrog,
+    RazličnikPiTri,
+    RazličnikPiPet,
+    RazličnikInženir,
+    RazličnikKlanec,
+    RazličnikMasaDa,
+    RazličnikMasaNe,
+    RazličnikEnota,
+    RazličnikKonec,
 }
 
 pub enum DialogueOutcome {      //outcomes that can happen after a dialogue option is chosen
     Continue(DialogueNodes),
     EndDialogue,
-    EndGame,
+    EndGame(Ending),
+    StartOralExam(DialogueNodes),       //starts the scoring track and continues to the first question
+    FinishOralExam,     //professor gives the grade, dialogue ends
+    Gamble {        //risky answer: which node comes next is decided by a roll
+        chance: i32,        //base success chance in percent
+        uses_meters: bool,      //if true, anxiety/attention (and the professor) change the chance
+        success: DialogueNodes,
+        failure: DialogueNodes,
+    },
 }
 
 
@@ -129,13 +299,37 @@ pub struct GameState {
     // pub spawn_y: f64,
 
     pub player: Player,     //everything about the player
-    pub pressed_keys: HashSet<String>,      //which keys are pressed
-    pub music_started: bool,        //yes/no so it doesn't restart every frame
+    pub pressed_keys: HashSet<String>,      //which keys are pressed (normalized, see input::normalize)
+    pub bindings: KeyBindings,      //which key does which action, saved in localStorage
+    pub settings: Settings,     //volume, text speed, display... also in localStorage
+    pub gamepad: GamepadInput,      //controller buttons and stick, read every tick
+    pub touch: TouchInput,      //on-screen joystick on phones
+    pub typewriter: Typewriter,     //how much of the current dialogue line is already shown
+    pub has_save: bool,     //so the pause menu doesn't read localStorage on every frame
+    pub audio: Audio,       //music channels and sound effects
     pub screen: Screen,     //above enum :)
+    pub clock: GameClock,       //in-game time of day
+    pub current_room: Room,     //which room the player is in
     pub walls: Vec<Wall>, // stene
-    pub interactive_items: Vec<Item>,    //vestor of all interactive items
+    pub interactive_items: Vec<Item>,    //vestor of all interactive items in the current room (without npcs)
+    pub npcs: Vec<Npc>,     //all npcs in every room, they walk around on their own
     pub interaction_state: InteractionState,      //when in interaction state
     pub nearby_item: Option<Interactable>,     //when we detect a nearby item with usize id
+    pub notification: Option<Notification>,     //feedback popup, only one at a time
+    pub day_started: u32,       //last day whose morning already happened (vouchers handed out etc.)
+    pub wake_up_pending: bool,      //the all-nighter ended in the middle of an oral exam, you wake up once the professor is done with you
+    pub purchases: Vec<Purchase>,       //everything bought at the counter this run
+    pub transactions: Vec<Transaction>,     //every money change this run, earnings and spending
+    pub rng: Rng,       //for everything random (exam questions, ...)
+    pub transcript: Transcript,     //letnik and every exam attempt this run, written and oral
+    pub mishaps: Vec<Mishap>,       //every nevšečnost from data/mishaps, loaded once
+    pub active_mishap: Option<ActiveMishap>,        //popup that stops the game until you click through it
+    pub mishap_log: Vec<MishapRecord>,      //which mishaps already happened this run
+    pub affinity: Affinity,     //how much every npc likes you
+    pub quests: QuestLog,       //naloge you got this run and how far you are with them
+    pub endings: EndingsGallery,        //endings found in any run so far, saved in localStorage
+    pub achievements: Achievements,     //dosežki from every run so far, also in localStorage
+    pub toasts: Vec<Notification>,      //"achievement unlocked" popups, shown one after another
 }
 
 
@@ -147,9 +341,25 @@ fn get_screen_size() -> (f64, f64) {
     (vw, vh)
 }
 
+fn spawn_npcs(character: Character) -> Vec<Npc> {     //your friend (whoever you're not playing as) and the professors
+    vec![Npc::new(character.friend()), Npc::new(NpcId::Peteršič), Npc::new(NpcId::Različnik)]
+}
+
 impl GameState {
     pub fn new() -> Self {      //creates a new game state, setting everything to default
-        let (vw, vh) = get_screen_size();       //browser screen size in pixels
+        let mut game_state = GameState::offline(get_screen_size());     //browser screen size in pixels
+        game_state.bindings = KeyBindings::load();
+        game_state.settings = Settings::load();
+        game_state.touch = TouchInput::new();
+        game_state.has_save = save::exists();
+        game_state.rng = Rng::from_time();
+        game_state.endings = EndingsGallery::load();
+        game_state.achievements = Achievements::load();
+        game_state
+    }
+
+    //the same without asking the browser for anything (nothing is loaded from localStorage), tests use it directly
+    pub fn offline((vw, vh): (f64, f64)) -> Self {
         let world_width = 1200.0;      //how wide and high the fixed window will be (we're interested in the ratio)
         let world_height = 600.0;       
         let scale = 1.0;
@@ -164,29 +374,38 @@ impl GameState {
             viewport_height: vh,
             scale,
             padding,
-            player: Player::new(100., 100.),        //where the player spawns, we need to change it so he spawns at the door :)
+            player: Player::new(100., 100., Character::Lan),        //where the player spawns, we need to change it so he spawns at the door :)
             pressed_keys: HashSet::new(),       //no keys pressed
-            music_started: false,       //so the default state is no music
+            bindings: KeyBindings::default_bindings(),
+            settings: Settings::default_settings(),
+            gamepad: GamepadInput::new(),
+            touch: TouchInput::off(),
+            typewriter: Typewriter::new(),
+            has_save: false,
+            audio: Audio::new(),        //no music until the first key press
             screen: Screen::Start,
-            walls: vec![
-                Wall { x: 60., y: 0., width: 390., height: 60. }, // pult
-                Wall { x: 1003., y: 595., width: 165., height: 12. }, // vrata
-                Wall { x: 50., y: 380., width: 80., height: 130. }, // miza spodaj prva
-                Wall { x: 260., y: 420., width: 70., height: 100. }, // miza spodaj druga
-                Wall { x: 440., y: 415., width: 90., height: 100. }, // miza spodaj tretja
-                Wall { x: 700., y: 370., width: 70., height: 200. }, // miza spodaj četrta
-                Wall { x: 200., y: 200., width: 100., height: 90. }, // miza zgoraj leva
-                Wall { x: 490., y: 190., width: 180., height: 90. }, // miza zgoraj desna
-                Wall { x: 530., y: 450., width: 36., height: 124. }, // ema
-                Wall { x: 800., y: 0., width: 400., height: 70. }, // pult 2
-            ],
-            interactive_items: vec![
-                Item { kind: Interactable::Object(Objects::Counter), x: 60., y: 10., width: 390., height: 65. },    //counter
-                Item { kind: Interactable::Object(Objects::Door), x: 1003., y: 595., width: 165., height: 12. },    //bottom door
-                Item { kind: Interactable::Npc(NpcId::Ema), x: 530., y: 450., width: 36., height: 124.}, // Ema as npc has item_id 2
-            ],
+            clock: GameClock::new(),
+            current_room: Room::Mafija,
+            walls: Room::Mafija.walls(),
+            interactive_items: Room::Mafija.items(),
+            npcs: spawn_npcs(Character::Lan),
             interaction_state: InteractionState::None,
-            nearby_item: None
+            nearby_item: None,
+            notification: None,
+            day_started: 1,
+            wake_up_pending: false,
+            purchases: vec![],
+            transactions: vec![],
+            rng: Rng::new(1),
+            transcript: Transcript::new(),
+            mishaps: mishap::all_mishaps(),
+            active_mishap: None,
+            mishap_log: vec![],
+            affinity: Affinity::new(),
+            quests: QuestLog::new(),
+            endings: EndingsGallery { discovered: vec![] },
+            achievements: Achievements { unlocked: vec![] },
+            toasts: vec![],
         }
     }
     pub fn update_viewport(&mut self) {
@@ -205,7 +424,7 @@ impl GameState {
         let container_aspect = container_width / container_height;      //calculating the new aspect ratio
 
         //now let's change the display depending on which aspect is bigger (because we don't want to stretch our display AKA change the game_aspect):
-        let (scaled_w, scaled_h, new_scale) = if game_aspect > container_aspect {      //so if container too high
+        let (mut scaled_w, mut scaled_h, mut new_scale) = if game_aspect > container_aspect {      //so if container too high
             let w = container_width;        //constrained by width
             let h = w / game_aspect;        //we want to always preserve the ratio
             let s = w / self.world_width;
@@ -219,12 +438,52 @@ impl GameState {
             (container_width, container_height, self.scale)
         };
         
+        //pixel-perfect: only whole number scales (2x, 3x...) so every pixel of the art is the same size, the rest stays black
+        if self.settings.pixel_perfect && new_scale >= 1.0 {
+            new_scale = new_scale.floor();
+            scaled_w = self.world_width * new_scale;
+            scaled_h = self.world_height * new_scale;
+        }
+
         //we can finally adjust the viewport (how big the screen displays on the device)
         self.viewport_width = scaled_w;
         self.viewport_height = scaled_h;
         self.scale = new_scale;     //scale = new / old;  by remembering how much we scaled the original world_width, we can scale all other objects :)
     }
 
+    //everything that has to go back to default when you start a new game (otherwise the game remembers the last run)
+    pub fn reset_run(&mut self, character: Character) {
+        self.player = Player::new(100., 100., character);       //fresh meters and backpack for whoever you picked
+        self.clock = GameClock::new();
+        self.day_started = self.clock.day;
+        self.wake_up_pending = false;
+        self.purchases.clear();
+        self.transactions.clear();
+        self.notification = None;
+        self.npcs = spawn_npcs(character);
+        self.transcript = Transcript::new();
+        self.active_mishap = None;
+        self.mishap_log.clear();
+        self.affinity = Affinity::new();
+        self.quests = QuestLog::new();
+        self.interaction_state = InteractionState::None;
+        self.enter_room(Room::Mafija);
+        self.player.x = 100.;       //the very first spawn stays where it was
+        self.player.y = 100.;
+        self.start_quest(QuestId::JutranjaKava);
+    }
+
+    pub fn enter_room(&mut self, room: Room) {      //swaps walls and items for the new room and puts the player at its door
+        self.current_room = room;
+        self.walls = room.walls();
+        self.interactive_items = room.items();
+        let (x, y) = room.entrance();
+        self.player.x = x;
+        self.player.y = y;
+        self.nearby_item = None;
+        self.check_quests();        //"go to room X" objectives
+    }
+
     pub fn collides_with_wall( // preverja a se hočeš premaknit nekam kjer je stena
         &self,
         next_x: f64,
@@ -232,88 +491,688 @@ impl GameState {
         pw: f64,
         ph: f64,
     ) -> bool {
-        for wall in &self.walls {
-            let no_overlap =
-                next_x + pw <= wall.x ||           
-                next_x >= wall.x + wall.width ||   
-                next_y + ph <= wall.y ||          
-                next_y >= wall.y + wall.height;
-
-            if !no_overlap {
-                return true;    
+        overlaps_any(&self.walls, next_x, next_y, pw, ph)
+    }
+
+    pub fn collides_with_npc(&self, next_x: f64, next_y: f64, pw: f64, ph: f64) -> bool {       //npcs in the same room block you like walls do
+        self.npcs
+            .iter()
+            .filter(|npc| npc.room == self.current_room)
+            .any(|npc| {
+                !(next_x + pw <= npc.x
+                    || next_x >= npc.x + npc.width
+                    || next_y + ph <= npc.y
+                    || next_y >= npc.y + npc.height)
+            })
+    }
+
+    pub fn items_in_room(&self) -> Vec<Item> {      //static items of the room + npcs that are currently standing in it
+        let mut items = self.interactive_items.clone();
+        items.extend(
+            self.npcs
+                .iter()
+                .filter(|npc| npc.room == self.current_room)
+                .map(|npc| Item { kind: Interactable::Npc(npc.id), x: npc.x, y: npc.y, width: npc.width, height: npc.height }),
+        );
+        items
+    }
+
+    pub fn update_npcs(&mut self) {     //called every tick: checks everyone's schedule and moves them a bit along their path
+        let minutes = self.clock.minutes;
+        let (world_width, world_height) = (self.world_width, self.world_height);
+
+        for npc in &mut self.npcs {
+            let entry = npc.current_entry(minutes);
+            let target = (entry.room, entry.x, entry.y);
+            if npc.destination != Some(target) {        //schedule moved on, so we plan a new path
+                npc.destination = Some(target);
+                npc.plan_path(world_width, world_height);
+            }
+
+            if npc.path.is_empty() && !npc.stuck && npc.room != target.0 {        //we reached the door, so we go through it into the next room
+                npc.room = target.0;
+                let (x, y) = target.0.entrance();
+                npc.x = x;
+                npc.y = y;
+                npc.plan_path(world_width, world_height);
             }
+
+            npc.step();
         }
-        false
     }
 
-    pub fn player_near_item(&self, threshold: f64) -> Option<Interactable> {
-        let px_min = self.player.x;
-        let px_max = self.player.x + self.player.width;
-        let py_min = self.player.y;
-        let py_max = self.player.y + self.player.height;
+    fn distance_to(&self, item: &Item) -> f64 {      //gap between the player's and the item's box, 0 if they touch
+        let dx = (item.x - (self.player.x + self.player.width))
+            .max(self.player.x - (item.x + item.width))
+            .max(0.0);
+
+        let dy = (item.y - (self.player.y + self.player.height))
+            .max(self.player.y - (item.y + item.height))
+            .max(0.0);
 
-        self.interactive_items
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    pub fn player_near_item(&self, threshold: f64) -> Option<Interactable> {
+        self.items_in_room()
             .iter()
-            .filter_map(|item| {
-                let ix_min = item.x;
-                let ix_max = item.x + item.width;
-                let iy_min = item.y;
-                let iy_max = item.y + item.height;
+            .map(|item| (item.kind, self.distance_to(item)))
+            .filter(|(_, dist)| *dist <= threshold)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(interactable, _)| interactable)
+    }
+
+    pub fn player_near(&self, interactable: Interactable, threshold: f64) -> bool {       //like player_near_item, but for one specific thing
+        self.items_in_room().iter().any(|item| item.kind == interactable && self.distance_to(item) <= threshold)
+    }
+
+    //F next to something: objects open their menu, npcs start talking
+    pub fn interact(&mut self, interactable: Interactable) {
+        match interactable {
+            Interactable::Object(Objects::Counter | Objects::Door | Objects::ExamDesk) => {
+                let options = self.menu_options_for_item(interactable);
+                match options.first() {
+                    Some(first) => {
+                        self.interaction_state = InteractionState::MenuOpen {
+                            interactable,
+                            selection: *first, //start from the first option
+                        };
+                    }
+                    None => self.notify("Letos nimaš več pisnih izpitov."),     //exam desk when all written parts are done
+                }
+            }
+            Interactable::Npc(npc) => {
+                self.interaction_state = InteractionState::Dialogue {
+                    npc,               //store the NPC
+                    node: self.dialogue_start(npc), //every npc has its own first line
+                    exam: None,     //professors start the exam only once you say you're ready
+                    selection: 0,
+                };
+                self.typewriter.reset();
+                self.audio.play(Sfx::Dialogue, &self.settings);
+                self.quest_event(QuestEvent::Talked(npc));
+            }
+        }
+    }
+
+    //click-to-move: plans a path around the walls so the player's feet end up where you clicked
+    pub fn walk_to(&mut self, x: f64, y: f64) {
+        let goal = (x - self.player.width / 2.0, y - self.player.height);
+        self.plan_walk(goal, None);
+    }
+
+    //walks up to an object or npc and opens its menu/dialogue once you're there
+    pub fn walk_up_to(&mut self, interactable: Interactable) {
+        let Some(item) = self.items_in_room().into_iter().find(|item| item.kind == interactable) else {
+            return;
+        };
+        let goal = (item.x + (item.width - self.player.width) / 2.0, item.y + (item.height - self.player.height) / 2.0);      //the middle of it, find_path stops at the closest free spot
+        self.plan_walk(goal, Some(interactable));
+    }
+
+    fn plan_walk(&mut self, goal: (f64, f64), target: Option<Interactable>) {
+        let (pw, ph) = (self.player.width, self.player.height);
+        self.player.path = find_path(&self.walls, self.world_width, self.world_height, (self.player.x, self.player.y), goal, pw, ph)
+            .unwrap_or_default();       //nowhere to go (ex. clicked inside a wall with no free spot around), stay put
+        self.player.walk_target = target;
+    }
 
-                let dx = (ix_min - px_max)
-                    .max(px_min - ix_max)
-                    .max(0.0);
+    pub fn notify(&mut self, text: impl Into<String>) {
+        let text = self.say(&text.into());
+        self.notification = Some(Notification { text, ticks_left: 180 });        //~3 seconds
+    }
+
+    pub fn say(&self, text: &str) -> String {       //dialogue and notifications are written with {ime} and [šel|šla], see Character::say
+        self.player.character.say(text)
+    }
 
-                let dy = (iy_min - py_max)
-                    .max(py_min - iy_max)
-                    .max(0.0);
+    pub fn tick_notification(&mut self) {
+        if let Some(notification) = &mut self.notification {
+            notification.ticks_left = notification.ticks_left.saturating_sub(1);
+            if notification.ticks_left == 0 {
+                self.notification = None;
+            }
+        }
+    }
+
+    pub fn tick_toasts(&mut self) {     //only the first toast is on screen, the next one comes when it's gone
+        if let Some(toast) = self.toasts.first_mut() {
+            toast.ticks_left = toast.ticks_left.saturating_sub(1);
+            if toast.ticks_left == 0 {
+                self.toasts.remove(0);
+            }
+        }
+    }
 
-                let dist = (dx * dx + dy * dy).sqrt();
+    //something happened that achievements might be waiting for, new ones get saved and shown as a toast
+    pub fn achievement_event(&mut self, event: AchievementEvent) {
+        for achievement in ALL_ACHIEVEMENTS {
+            if self.achievements.has(achievement) || !achievement.unlocked_by(event, self) {
+                continue;
+            }
+            self.achievements.unlock(achievement);
+            let text = format!("Dosežek odklenjen: {}", self.say(achievement.title()));
+            self.toasts.push(Notification { text, ticks_left: 240 });       //~4 seconds
+        }
+    }
 
-                if dist <= threshold {
-                    Some((item.kind, dist))
+    //checked every tick, so it works no matter how the clock got to the next day
+    pub fn check_new_day(&mut self) {
+        if self.wake_up_pending && !matches!(self.interaction_state, InteractionState::Dialogue { exam: Some(_), .. }) {
+            self.wake_up_pending = false;
+            self.start_minigame(Box::new(WakeUp::new()));       //would throw the exam dialogue away, so it waits until the exam is over
+        }
+        if self.clock.day == self.day_started {
+            return;
+        }
+        self.day_started = self.clock.day;
+
+        //new morning, new vouchers: the allowance gets topped up, leftovers from yesterday don't stack
+        let inventory = &mut self.player.inventory;
+        let vouchers = inventory.count(ItemKind::BonZaTortiljo);
+        if vouchers < DAILY_VOUCHERS {
+            inventory.add(ItemKind::BonZaTortiljo, DAILY_VOUCHERS - vouchers);
+        } else {
+            inventory.remove(ItemKind::BonZaTortiljo, vouchers - DAILY_VOUCHERS);
+        }
+        let all_nighter = self.player.attention < wake_up::ALL_NIGHTER_ATTENTION;      //you were still up and dead tired at midnight
+        self.player.attention = self.player.max_attention;      //you slept, so you're fresh again
+
+        if (self.clock.day - 1).is_multiple_of(economy::ALLOWANCE_EVERY_DAYS) {     //day 8, 15, 22... is allowance day
+            self.earn(economy::WEEKLY_ALLOWANCE, TransactionKind::Allowance);
+            self.notify(format!("Dan {}: [dobil|dobila] si {} bona in {} € žepnine!", self.clock.day, DAILY_VOUCHERS, economy::WEEKLY_ALLOWANCE));
+        } else {
+            self.notify(format!("Dan {}: [dobil|dobila] si {} bona za tortiljo.", self.clock.day, DAILY_VOUCHERS));
+        }
+        if all_nighter {
+            self.wake_up_pending = true;        //starts on the next tick
+        } else {
+            self.roll_mishaps(Trigger::Morning);        //ex. oversleeping (after an all-nighter you already did that)
+        }
+    }
+
+    pub fn music_track(&self) -> Track {        //exams get their own theme, otherwise it depends on the room
+        match &self.screen {
+            Screen::MiniGame(game) => game.music().unwrap_or(Track::for_room(self.current_room)),
+            _ if matches!(self.interaction_state, InteractionState::Dialogue { exam: Some(_), .. }) => Track::Exam,
+            _ => Track::for_room(self.current_room),
+        }
+    }
+
+    pub fn music_anxiety(&self) -> f64 {       //0-1, only during a run, the menus stay calm
+        match self.screen {
+            Screen::Playing | Screen::Paused { .. } | Screen::MiniGame(_) => self.player.anxiety as f64 / self.player.max_anxiety.max(1) as f64,
+            _ => 0.0,
+        }
+    }
+
+    pub fn start_minigame(&mut self, mut game: Box<dyn MiniGame>) {
+        self.pressed_keys.clear();      //otherwise you keep walking after the mini-game if you were holding a key
+        self.interaction_state = InteractionState::None;
+        game.init(self);
+        self.screen = Screen::MiniGame(game);
+    }
+
+    pub fn finish_minigame(&mut self, result: MiniGameResult) {     //back to walking around, the result decides what changes
+        self.screen = Screen::Playing;
+        match result {
+            MiniGameResult::WakeUp { minutes_late, anxiety } => self.finish_wake_up(minutes_late, anxiety),
+            MiniGameResult::WrittenExam { subject, percent, passed } => self.finish_written_exam(subject, percent, passed),
+        }
+    }
+
+    fn finish_wake_up(&mut self, minutes_late: u32, anxiety: i32) {      //you made it out of bed, how fast decides how you start the day
+        self.player.anxiety = anxiety.min(self.player.max_anxiety);
+        self.enter_room(Room::Mafija);      //you run straight to faks
+        self.achievement_event(AchievementEvent::WokeUp { minutes_late });
+        if minutes_late == 0 {
+            self.notify("[Vstal|Vstala] si takoj, ko je zazvonila budilka!");
+        } else {
+            self.notify(format!("[Zaspal|Zaspala] si {} min. Na faks prideš ob {:02}:{:02}.", minutes_late, self.clock.hour(), self.clock.minute()));
+        }
+    }
+
+    //goes through the mishaps of this trigger in file order, the first one whose roll succeeds gets shown
+    pub fn roll_mishaps(&mut self, trigger: Trigger) {
+        if self.active_mishap.is_some() {
+            return;
+        }
+        for index in 0..self.mishaps.len() {
+            if self.mishaps[index].trigger != trigger || !self.mishap_possible(index) {
+                continue;
+            }
+            let probability = self.mishaps[index].probability();
+            if self.rng.chance(probability) {
+                self.start_mishap(index);
+                return;
+            }
+        }
+    }
+
+    fn mishap_possible(&self, index: usize) -> bool {       //right place, right time, right mood, and not already today
+        let mishap = &self.mishaps[index];
+        let happened_today = self.mishap_log.iter().any(|record| record.day == self.clock.day && record.index == index);
+        !happened_today
+            && mishap.room.is_none_or(|room| room == self.current_room)
+            && mishap.in_time_window(self.clock.minutes)
+            && mishap.conditions.iter().all(|condition| self.condition_holds(condition))
+    }
+
+    fn start_mishap(&mut self, index: usize) {      //effects happen right away, the popup just tells you about them
+        self.mishap_log.push(MishapRecord { day: self.clock.day, minutes: self.clock.minutes, index });
+        for effect in self.mishaps[index].effects.clone() {
+            self.apply_mishap_effect(effect);
+        }
+        self.pressed_keys.clear();      //otherwise you keep walking after the popup closes
+        self.active_mishap = Some(ActiveMishap { index, page: 0 });
+    }
+
+    fn apply_mishap_effect(&mut self, effect: MishapEffect) {
+        match effect {
+            MishapEffect::Anxiety(amount) if amount < 0 => self.player.get_less_anxious(-amount),
+            MishapEffect::Anxiety(amount) => self.player.get_more_anxious(amount),
+            MishapEffect::Attention(amount) if amount < 0 => self.player.lose_attention(-amount),
+            MishapEffect::Attention(amount) => self.player.gain_attention(amount),
+            MishapEffect::Money(amount) if amount < 0 => self.pay(-amount, TransactionKind::Mishap),
+            MishapEffect::Money(amount) => self.earn(amount, TransactionKind::Mishap),
+            MishapEffect::Time(minutes) => self.clock.advance_minutes(minutes),
+            MishapEffect::Lose(kind) => {
+                self.player.inventory.remove(kind, 1);
+            }
+            MishapEffect::Get(kind) => {
+                self.player.inventory.add(kind, 1);
+            }
+        }
+    }
+
+    pub fn continue_mishap(&mut self) {     //next page of the popup, closes it after the last one
+        let Some(active) = &mut self.active_mishap else {
+            return;
+        };
+        active.page += 1;
+        if active.page >= self.mishaps[active.index].pages.len() {
+            self.active_mishap = None;
+        }
+    }
+
+    //all money changes go through these two so the transaction history is always complete
+    pub fn earn(&mut self, amount: i32, kind: TransactionKind) {
+        self.player.earn_money(amount);
+        self.transactions.push(Transaction { day: self.clock.day, minutes: self.clock.minutes, amount, kind });
+    }
+
+    pub fn pay(&mut self, amount: i32, kind: TransactionKind) {
+        let before = self.player.money;
+        self.player.spend_money(amount);
+        let lost = before - self.player.money;      //money can't go below 0, so a big mishap takes less than it says
+        if lost > 0 {
+            self.transactions.push(Transaction { day: self.clock.day, minutes: self.clock.minutes, amount: -lost, kind });
+        }
+    }
+
+    pub fn work_shift(&mut self) {      //costs time, attention and a bit of nerves, but pays
+        if self.clock.minutes > economy::LAST_SHIFT_START {
+            self.notify("Mafija se kmalu zapre, danes ni več izmen.");
+            return;
+        }
+        if self.player.attention < economy::SHIFT_ATTENTION_COST {
+            self.notify("[Preutrujen|Preutrujena] si za izmeno. Popij kavo ali pojdi spat.");
+            return;
+        }
+        self.clock.advance_minutes(economy::SHIFT_MINUTES);
+        self.player.lose_attention(economy::SHIFT_ATTENTION_COST);
+        self.player.get_more_anxious(economy::SHIFT_ANXIETY_COST);
+        self.earn(economy::SHIFT_PAY, TransactionKind::Shift);
+        self.achievement_event(AchievementEvent::WorkedShift);
+        self.notify(format!("[Odpracoval|Odpracovala] si izmeno: +{} €", economy::SHIFT_PAY));
+    }
+
+    pub fn sell_book(&mut self) {
+        if !self.player.inventory.remove(ItemKind::Knjiga, 1) {
+            self.notify("Nimaš nobene knjige za prodat.");
+            return;
+        }
+        self.earn(economy::BOOK_RESALE_PRICE, TransactionKind::BookSold);
+        self.notify(format!("[Prodal|Prodala] si knjigo: +{} €", economy::BOOK_RESALE_PRICE));
+    }
+
+    //INTERACTIVE FUNCTIONS:
+    pub fn buy(&mut self, item: ShopItem, with_voucher: bool) {     //buying at the counter, refuses (with a message) if you can't pay
+        let payment = if with_voucher {
+            if !item.accepts_voucher() {
+                self.notify(format!("{} se ne da plačati z bonom.", item.name()));
+                return;
+            }
+            if !self.player.inventory.remove(ItemKind::BonZaTortiljo, 1) {
+                self.notify("Danes nimaš več bonov.");
+                return;
+            }
+            Payment::Voucher
+        } else {
+            if self.player.money < item.price() {
+                self.notify(format!("Premalo denarja: {} stane {} €, ti imaš {} €.", item.name(), item.price(), self.player.money));
+                return;
+            }
+            self.pay(item.price(), TransactionKind::Purchase(item));
+            Payment::Money(item.price())
+        };
+
+        self.consume(item);
+        self.purchases.push(Purchase { day: self.clock.day, minutes: self.clock.minutes, item, payment });
+        self.audio.play(Sfx::Purchase, &self.settings);
+        self.quest_event(QuestEvent::Bought(item));
+        self.achievement_event(AchievementEvent::Consumed(item));
+    }
+
+    fn consume(&mut self, item: ShopItem) {     //eating/drinking something from the counter, no matter who paid
+        let (change, attention) = self.player.character.item_effect(item);      //quirks can make it stronger
+        if change < 0 {
+            self.player.get_less_anxious(-change);
+        } else {
+            self.player.get_more_anxious(change);
+        }
+        self.player.gain_attention(attention);
+    }
+
+    pub fn use_item(&mut self, kind: ItemKind) {        //using an item from the inventory screen
+        let Some(effect) = kind.use_effect() else {
+            return;
+        };
+        if effect.consumed && !self.player.inventory.remove(kind, 1) {
+            return;
+        }
+        if effect.anxiety < 0 {
+            self.player.get_less_anxious(-effect.anxiety);
+        } else {
+            self.player.get_more_anxious(effect.anxiety);
+        }
+    }
+
+    pub fn response_available(&self, response: &DialogueResponse) -> bool {     //hides responses that need an item you don't have (or shouldn't have)
+        response.condition.as_ref().is_none_or(|condition| self.condition_holds(condition))
+    }
+
+    pub fn choose_pause_option(&mut self, option: PauseOption) {
+        match option {
+            PauseOption::Resume => self.screen = Screen::Playing,
+            PauseOption::Settings => self.screen = Screen::Settings { selection: SettingsRow::MusicVolume, from_pause: true },
+            PauseOption::Save => {
+                save::save(self);
+                self.has_save = true;
+                self.notify("Igra shranjena.");
+            }
+            PauseOption::Load => {
+                self.has_save = save::load(self);
+                if self.has_save {
+                    self.screen = Screen::Playing;
+                    self.notify("Igra naložena.");
                 } else {
-                    None
+                    self.notify("Ni shranjene igre.");
                 }
-            })
-            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
-            .map(|(interactable, _)| interactable)
+            }
+            PauseOption::Quit => self.screen = Screen::Start,
+        }
     }
- 
 
+    pub fn dialogue_line(&self, npc: NpcId, node: DialogueNodes) -> String {        //what the npc says on this node, with your name filled in
+        GameState::npc_dialogue(npc).get(&node).map(|node| self.say(node.text)).unwrap_or_default()
+    }
 
-    //INTERACTIVE FUNCTIONS:
-    pub fn buy_coffee(&mut self) {
-        if self.player.money >= 3 {
-            self.player.spend_money(2);
-            self.player.get_more_anxious(5);
+    pub fn response_count(&self, npc: NpcId, node: DialogueNodes) -> usize {       //how many responses are shown on this node
+        GameState::npc_dialogue(npc)
+            .get(&node)
+            .map(|node| node.responses.iter().filter(|r| self.response_available(r)).count())
+            .unwrap_or(0)
+    }
+
+    pub fn condition_holds(&self, condition: &Condition) -> bool {
+        match *condition {
+            Condition::Has(kind) => self.player.inventory.has(kind),
+            Condition::Missing(kind) => !self.player.inventory.has(kind),
+            Condition::AnxietyBelow(limit) => self.player.anxiety < limit,
+            Condition::AnxietyAtLeast(limit) => self.player.anxiety >= limit,
+            Condition::AttentionAtLeast(limit) => self.player.attention >= limit,
+            Condition::AttentionBelow(limit) => self.player.attention < limit,
+            Condition::ReadyForOral(subject) => self.transcript.ready_for_oral(subject),
+            Condition::NotReadyForOral(subject) => !self.transcript.ready_for_oral(subject),
+            Condition::FavorReady(npc) => self.affinity.favor_ready(npc, self.clock.day),
         }
-        else {}
     }
 
-    pub fn buy_tortilla(&mut self) {
-        self.player.spend_money(5);
-        self.player.get_less_anxious(8);
+    pub fn apply_dialogue_effects(&mut self, npc: NpcId, effects: &[DialogueEffect]) {
+        for effect in effects {
+            match effect {
+                DialogueEffect::GiveItem(kind) => {
+                    self.player.inventory.add(*kind, 1);
+                }
+                DialogueEffect::TakeItem(kind) => {
+                    self.player.inventory.remove(*kind, 1);
+                }
+                DialogueEffect::Affinity(amount) => {
+                    self.affinity.change(npc, *amount);
+                    self.achievement_event(AchievementEvent::AffinityChanged(npc));
+                }
+                DialogueEffect::Treat(item) => {
+                    self.affinity.use_favor(npc, self.clock.day);
+                    self.consume(*item);
+                    self.purchases.push(Purchase { day: self.clock.day, minutes: self.clock.minutes, item: *item, payment: Payment::Treat(npc) });
+                    self.achievement_event(AchievementEvent::Consumed(*item));
+                }
+                DialogueEffect::StartQuest(id) => self.start_quest(*id),
+            }
+        }
     }
 
-    pub fn smoke(&mut self) {    //smoking calms you down but maybe there's an increasing chance of having a panic attack (Game Over)
+    //živčen želodec: before every exam you first have to run to the WC
+    pub fn exam_nerves(&mut self) {
+        if !self.player.character.has(Quirk::ŽivčenŽelodec) {
+            return;
+        }
+        self.clock.advance_minutes(NERVES_MINUTES);
+        self.player.lose_attention(10);
         self.player.get_less_anxious(15);
+        self.notify("Pred izpitom si [moral|morala] na WC bruhat. Zdaj ti je malo lažje.");
+    }
+
+    pub fn start_written_exam(&mut self, subject: Subject) {
+        self.exam_nerves();
+        self.roll_mishaps(Trigger::WrittenExam);        //happens before the sheet is handed out, so it already counts for the exam
+        self.start_minigame(Box::new(WrittenExam::new(subject)));
+    }
+
+    fn finish_written_exam(&mut self, subject: Subject, percent: u32, passed: bool) {     //back to the classroom, the result changes how you feel
+        self.transcript.attempts.push(ExamAttempt::new(self.clock.day, subject, false, percent));
+        self.clock.advance_minutes(60);     //writing takes an hour
+        self.player.lose_attention(20);
+        if passed {
+            self.player.get_less_anxious(10);
+            self.achievement_event(AchievementEvent::ExamPassed);
+            let next = if subject.has_oral() { ", zdaj pa še ustni v kabinetu" } else { "" };
+            self.notify(format!("{}: {} % (ocena {}) - [opravil|opravila] si pisni izpit{}!", subject.name(), percent, transcript::grade(percent), next));
+        } else {
+            self.player.get_more_anxious(15);
+            self.notify(format!("{}: {} % - [padel|padla] si pisni izpit.", subject.name(), percent));
+        }
+        self.check_progress();
+    }
+
+    //success chance of a risky answer in an oral exam, shown next to the answer and used for the roll
+    pub fn gamble_chance(&self, npc: NpcId, chance: i32, uses_meters: bool) -> i32 {
+        let player = &self.player;
+        let attention_ratio = player.attention as f64 / player.max_attention as f64;
+        let anxiety_ratio = player.anxiety as f64 / player.max_anxiety as f64;
+        oral_exam::gamble_chance(chance, uses_meters, oral_exam::professor(npc).as_ref(), attention_ratio, anxiety_ratio)
+    }
+
+    pub fn finish_oral_exam(&mut self, exam: OralExam) {        //the professor decides, the result goes next to the written ones
+        let Some(professor) = oral_exam::professor(exam.professor) else {
+            return;
+        };
+        let percent = professor.percent(exam.score);
+        let passed = professor.passes(exam.score);
+        self.transcript.attempts.push(ExamAttempt::new(self.clock.day, professor.subject, true, percent));
+
+        self.clock.advance_minutes(30);     //ustni je krajši od pisnega
+        if passed {
+            self.player.get_less_anxious(15);
+            self.achievement_event(AchievementEvent::ExamPassed);
+            self.notify(format!("{}: [opravil|opravila] si ustni izpit pri {} ({} točk)!", professor.subject.name(), professor.name, exam.score));
+        } else {
+            self.player.get_more_anxious(20);
+            self.notify(format!("{}: {} te je vrgel na ustnem ({} točk).", professor.subject.name(), professor.name, exam.score));
+        }
+        self.check_progress();
+    }
+
+    //after every exam: if the whole year is passed you move on, after the last year you graduate
+    pub fn check_progress(&mut self) {
+        if self.transcript.graduated() {
+            self.interaction_state = InteractionState::None;
+            self.endings.discover(Ending::Diploma);
+            self.achievement_event(AchievementEvent::Ended(Ending::Diploma));
+            self.screen = Screen::Graduated;
+            return;
+        }
+        if self.transcript.advance_year() {
+            self.notify(format!("[Opravil|Opravila] si vse izpite! [Vpisan|Vpisana] si v {}. letnik.", self.transcript.year));
+        }
+    }
+
+    pub fn start_quest(&mut self, id: QuestId) {
+        if self.quests.start(id, self.clock.day, self.clock.minutes, self.player.character.friend()) {
+            self.notify(format!("Nova naloga: {} ({})", id.title(), self.bindings.label(Action::QuestLog)));
+            self.check_quests();        //maybe the first objective is already done (ex. you're already in the right room)
+        }
+    }
+
+    //something happened that objectives might be waiting for (talking, buying...)
+    pub fn quest_event(&mut self, event: QuestEvent) {
+        for index in 0..self.quests.quests.len() {
+            let quest = &self.quests.quests[index];
+            if quest.status == QuestStatus::Active && quest.objective().is_some_and(|objective| objective.done_by(event)) {
+                self.advance_quest(index);
+            }
+        }
+        self.check_quests();
+    }
+
+    //objectives that depend on where you are and what time it is, checked every in-game minute and after every quest event
+    pub fn check_quests(&mut self) {
+        for index in 0..self.quests.quests.len() {
+            let quest = &mut self.quests.quests[index];
+            if quest.status != QuestStatus::Active {
+                continue;
+            }
+            if let Some(Objective::BeIn { room, .. }) = quest.objective() {
+                if quest.deadline.is_some_and(|deadline| (self.clock.day, self.clock.minutes) >= deadline) {
+                    quest.status = QuestStatus::Failed;
+                    let title = quest.id.title();
+                    self.notify(format!("Naloga ni uspela: {}", title));
+                } else if room == self.current_room {
+                    self.advance_quest(index);
+                }
+            }
+        }
+    }
+
+    fn advance_quest(&mut self, index: usize) {
+        let (day, minutes, quest) = (self.clock.day, self.clock.minutes, &mut self.quests.quests[index]);
+        quest.step += 1;
+        quest.deadline = quest.objective().and_then(|objective| objective.deadline(day, minutes));
+        let id = quest.id;
+        match quest.objective() {
+            Some(next) => self.notify(format!("{}: {}", id.title(), next.label())),
+            None => {
+                quest.status = QuestStatus::Completed;
+                let rewards = id.rewards();
+                for reward in &rewards {
+                    self.apply_reward(*reward);
+                }
+                let labels: Vec<String> = rewards.iter().map(|reward| reward.label()).collect();
+                self.notify(format!("Naloga opravljena: {} ({})", id.title(), labels.join(", ")));
+            }
+        }
+    }
+
+    fn apply_reward(&mut self, reward: Reward) {
+        match reward {
+            Reward::Money(amount) => self.earn(amount, TransactionKind::QuestReward),
+            Reward::Attention(amount) => self.player.gain_attention(amount),
+            Reward::Anxiety(amount) if amount < 0 => self.player.get_less_anxious(-amount),
+            Reward::Anxiety(amount) => self.player.get_more_anxious(amount),
+        }
+    }
+
+    pub fn smoke(&mut self) {    //smoking calms you down but maybe there's an increasing chance of having a panic attack (Game Over)
+        self.player.get_less_anxious(self.player.character.smoke_relief());
     }
 
     pub fn go_home(&mut self) {
-        self.screen = Screen::GameOver; //this should change to /Home in the future when we draw it but now it could be /GameOver
+        self.end_game(Ending::Domov); //this should change to /Home in the future when we draw it but now it could be /GameOver
     }
 
-    pub fn menu_options_for_item(interactable: Interactable) -> Vec<MenuOption> {    //only handling the menu items here (not dialogue or anything)
-        match interactable {
-            Interactable::Object(Objects::Counter) => vec![MenuOption::Coffee, MenuOption::Tortilla],        //the null object still remains to be the counter
-            Interactable::Object(Objects::Door) => vec![MenuOption::Smoke, MenuOption::GoHome],       //the first interactive object is the door
+    pub fn end_game(&mut self, ending: Ending) {        //game over screen for this ending, and it goes into the gallery
+        self.interaction_state = InteractionState::None;
+        self.endings.discover(ending);
+        self.achievement_event(AchievementEvent::Ended(ending));
+        self.screen = Screen::GameOver(ending);
+    }
+
+    //maxed out anxiety: if the last thing you had was a coffee less than half an hour ago, the coffee did it
+    pub fn panic_ending(&self) -> Ending {
+        match self.purchases.last() {
+            Some(purchase) if purchase.item == ShopItem::Kava && purchase.day == self.clock.day && self.clock.minutes < purchase.minutes + 30 => Ending::PrevečKave,
+            _ => Ending::PaničniNapad,
+        }
+    }
+
+    pub fn menu_options_for_item(&self, interactable: Interactable) -> Vec<MenuOption> {    //only handling the menu items here (not dialogue or anything)
+        match (interactable, self.current_room) {
+            (Interactable::Object(Objects::Counter), _) => {        //the null object still remains to be the counter, now with the whole price list
+                let mut options = vec![];
+                for item in COUNTER_ITEMS {
+                    options.push(MenuOption::Buy(item));
+                    if item.accepts_voucher() {
+                        options.push(MenuOption::BuyWithVoucher(item));
+                    }
+                }
+                options.push(MenuOption::WorkShift);
+                if self.player.inventory.has(ItemKind::Knjiga) {
+                    options.push(MenuOption::SellBook);
+                }
+                options
+            }
+            (Interactable::Object(Objects::Door), Room::Mafija) => vec![MenuOption::Smoke, MenuOption::GoToRoom(Room::DrugiŠtuk), MenuOption::GoHome],       //the first interactive object is the door
+            (Interactable::Object(Objects::Door), Room::DrugiŠtuk) => vec![MenuOption::GoToRoom(Room::Mafija), MenuOption::GoToRoom(Room::Kabinet)],
+            (Interactable::Object(Objects::Door), Room::Kabinet) => vec![MenuOption::GoToRoom(Room::DrugiŠtuk)],
+            (Interactable::Object(Objects::ExamDesk), _) => self      //only this year's subjects whose written part isn't done yet
+                .transcript
+                .enrolled()
+                .into_iter()
+                .filter(|s| !self.transcript.written_passed(*s))
+                .map(MenuOption::WriteExam)
+                .collect(),
             _ => vec![],
         }
     }
 
 
 
+pub fn dialogue_start(&self, npc: NpcId) -> DialogueNodes {     //first node when you press F next to an npc, depends on how much they like you
+    let affinity = self.affinity.get(npc);
+    let favor = self.condition_holds(&Condition::FavorReady(npc));      //same check as the "yes" answer, after today's favor they just say hi
+    match npc {
+        NpcId::Ema if favor => DialogueNodes::EmaVesela,
+        NpcId::Ema if affinity <= affinity::UPSET => DialogueNodes::EmaJezna,
+        NpcId::Ema => DialogueNodes::Živjo,
+        NpcId::Lan if favor => DialogueNodes::LanVesel,
+        NpcId::Lan if affinity <= affinity::UPSET => DialogueNodes::LanJezen,
+        NpcId::Lan => DialogueNodes::LanŽivjo,
+        NpcId::Peteršič => DialogueNodes::PeteršičZačetek,
+        NpcId::Različnik => DialogueNodes::RazličnikZačetek,
+    }
+}
+
 pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {       //we instead opt now for a hash map because vectors are usually used when we have something ordered (linearly) which isn't the case here since we have kind of like a directed graph (which isn't ordered)
     let mut map = HashMap::new();   //initiating a new hashmap
 
@@ -322,24 +1181,102 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
             map.insert(
                 DialogueNodes::Živjo,   //node label
                 DialogueNode {
-                    text: "Živjo Lan!!!",   //text under node label
+                    text: "Živjo {ime}!!!",   //text under node label
                     responses: vec![
                         DialogueResponse {      //top most response option
                             text: "Živjo Ema!!",    //text it shows for this option
                             outcome: DialogueOutcome::Continue(DialogueNodes::KajPočenjaš),     //what the outcome is if you choose it
+                            ..Default::default()
                         },
                         DialogueResponse {      //second most top response option
                             text: "Ema! Kaj delaš tukaj?",
                             outcome: DialogueOutcome::Continue(DialogueNodes::AhSajVes),
+                            ..Default::default()
+                        },
+                        DialogueResponse {
+                            text: "Kdo si ti?",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::LanASiVRedu),
+                            ..Default::default()
+                        },
+                    ],
+                },
+            );
+
+            //greetings that depend on how much Ema likes you (see GameState::dialogue_start)
+            map.insert(
+                DialogueNodes::EmaVesela,
+                DialogueNode {
+                    text: "{ime}!!! Ravno sem mislila nate. A ti lahko dam za kavo?",
+                    responses: vec![
+                        DialogueResponse {      //favor, once per day
+                            text: "Ja, hvala!",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::EmaČastim),
+                            condition: Some(Condition::FavorReady(NpcId::Ema)),
+                            effects: vec![DialogueEffect::Treat(ShopItem::Kava)],
+                            ..Default::default()
+                        },
+                        DialogueResponse {
+                            text: "Ne, hvala. Kaj pa ti delaš tukaj?",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::AhSajVes),
+                            ..Default::default()
                         },
                         DialogueResponse {
                             text: "Kdo si ti?",
                             outcome: DialogueOutcome::Continue(DialogueNodes::LanASiVRedu),
+                            ..Default::default()
+                        },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::EmaČastim,
+                DialogueNode {
+                    text: "Izvoli! Ampak jutri častiš ti.",
+                    responses: vec![
+                        DialogueResponse { text: "Dogovorjeno.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva), ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::EmaJezna,
+                DialogueNode {
+                    text: "Oh. Ti. Ne vem, če se mi da s tabo govorit.",
+                    responses: vec![
+                        DialogueResponse {
+                            text: "Oprosti, Ema. Nisem mislil resno.",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::EmaOdpustila),
+                            effects: vec![DialogueEffect::Affinity(15)],
+                            ..Default::default()
+                        },
+                        DialogueResponse {      //a book for the projektna is the best apology
+                            text: "Prinesel sem ti knjigo za Programiranje 2. Oprosti.",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLan),
+                            condition: Some(Condition::Has(ItemKind::Knjiga)),
+                            effects: vec![DialogueEffect::TakeItem(ItemKind::Knjiga), DialogueEffect::Affinity(30)],
+                            ..Default::default()
+                        },
+                        DialogueResponse {
+                            text: "Kakor hočeš.",
+                            outcome: DialogueOutcome::EndDialogue,
+                            effects: vec![DialogueEffect::Affinity(-5)],
+                            ..Default::default()
                         },
                     ],
                 },
             );
 
+            map.insert(
+                DialogueNodes::EmaOdpustila,
+                DialogueNode {
+                    text: "No prav. Ampak samo še tokrat.",
+                    responses: vec![
+                        DialogueResponse { text: "Hvala.", outcome: DialogueOutcome::Continue(DialogueNodes::KajPočenjaš), ..Default::default() },
+                    ],
+                },
+            );
+
             map.insert(
                 DialogueNodes::AhSajVes,
                 DialogueNode {
@@ -348,10 +1285,21 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                         DialogueResponse {
                             text: "J***m ti mater, Ema, zakaj samo jaz delam to projektno??.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::Oprosti),
+                            effects: vec![DialogueEffect::Affinity(-10)],
+                            ..Default::default()
                         },
                         DialogueResponse {
                             text: "Uživaj, življenje je kratko.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::TiSiTako),
+                            effects: vec![DialogueEffect::Affinity(3)],
+                            ..Default::default()
+                        },
+                        DialogueResponse {      //only if you have a book with you, Ema keeps it
+                            text: "Izvoli, sposodil sem si knjigo za Programiranje 2. Zdaj nimaš več izgovora.",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLan),
+                            condition: Some(Condition::Has(ItemKind::Knjiga)),
+                            effects: vec![DialogueEffect::TakeItem(ItemKind::Knjiga), DialogueEffect::Affinity(15)],
+                            ..Default::default()
                         },
                     ],
                 },
@@ -365,10 +1313,14 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                         DialogueResponse {
                             text: "V redu je, oprostim ti.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLan),
+                            effects: vec![DialogueEffect::Affinity(10)],
+                            ..Default::default()
                         },
                         DialogueResponse {
                             text: "Ne oprostim ti.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::Ok),
+                            effects: vec![DialogueEffect::Affinity(-10)],
+                            ..Default::default()
                         },
                     ],
                 },
@@ -377,11 +1329,34 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
             map.insert(
                 DialogueNodes::HvalaLan,
                 DialogueNode {
-                    text: "Hvala, Lan! Sem vedela, da se lahko zanesem nate. <3",
+                    text: "Hvala, {ime}! Sem vedela, da se lahko zanesem nate. <3",
                     responses: vec![
                         DialogueResponse {
                             text: "Ni za kaj. Zdaj pa pojdi delat!!!",
                             outcome: DialogueOutcome::EndDialogue,
+                            effects: vec![DialogueEffect::StartQuest(QuestId::Projektna)],
+                            ..Default::default()
+                        },
+                        DialogueResponse {
+                            text: "Ej, a si morda kje videla moj list s formulami?",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::NašlaSemList),
+                            condition: Some(Condition::Missing(ItemKind::ListSFormulami)),
+                            ..Default::default()
+                        },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::NašlaSemList,
+                DialogueNode {
+                    text: "Ja, pod mizo je ležal! Izvoli, pazi nanj, brez njega ne boš naredil izpita.",
+                    responses: vec![
+                        DialogueResponse {
+                            text: "Hvala, rešila si mi življenje.",
+                            outcome: DialogueOutcome::EndDialogue,
+                            effects: vec![DialogueEffect::GiveItem(ItemKind::ListSFormulami)],
+                            ..Default::default()
                         },
                     ],
                 },
@@ -395,10 +1370,14 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                         DialogueResponse {
                             text: "Saj sem se samo hecal.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLan),
+                            effects: vec![DialogueEffect::Affinity(5)],
+                            ..Default::default()
                         },
                         DialogueResponse {
                             text: "Zdaj bom šel stran, ker te ne maram.",
                             outcome: DialogueOutcome::EndDialogue,
+                            effects: vec![DialogueEffect::Affinity(-20)],
+                            ..Default::default()
                         },
                     ],
                 },
@@ -412,10 +1391,12 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                         DialogueResponse {
                             text: "Morda bova zaradi tega dobila slabšo oceno.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::NeToSe),
+                            ..Default::default()
                         },
                         DialogueResponse {
                             text: "Dobesedno nič.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLanTiSiMoj),
+                            ..Default::default()
                         },
                     ],
                 },
@@ -429,10 +1410,14 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                         DialogueResponse {
                             text: "Uau, kako dobra ideja, to bo gotovo delovalo!",
                             outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLanTiSiMoj),
+                            effects: vec![DialogueEffect::Affinity(5)],
+                            ..Default::default()
                         },
                         DialogueResponse {
                             text: "Ema, to se ne bo zgodilo.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::Ok2),
+                            effects: vec![DialogueEffect::Affinity(-5)],
+                            ..Default::default()
                         },
                     ],
                 },
@@ -441,11 +1426,12 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
             map.insert(
                 DialogueNodes::HvalaLanTiSiMoj,
                 DialogueNode {
-                    text: "Hvala, Lan, ti si moj največji podpornik!",
+                    text: "Hvala, {ime}, ti si moj največji podpornik!",
                     responses: vec![
                         DialogueResponse {
                             text: "Itak, da sem. No, uživaj, moram it.",
                             outcome: DialogueOutcome::EndDialogue,
+                            ..Default::default()
                         },
                     ],
                 },
@@ -459,8 +1445,10 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                         DialogueResponse {
                             text: "Saj sem se samo hecal.",
                             outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLanTiSiMoj),
+                            effects: vec![DialogueEffect::Affinity(5)],
+                            ..Default::default()
                         },
-                        DialogueResponse { text: "Zdaj bom šel stran, ker te ne maram.", outcome: DialogueOutcome::EndDialogue},
+                        DialogueResponse { text: "Zdaj bom šel stran, ker te ne maram.", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(-20)], ..Default::default() },
                     ],
                 },
             );
@@ -470,8 +1458,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Uf, še dobro. Tole je bilo zdaj malo čudno. A se počutiš v redu?",
                     responses: vec![
-                        DialogueResponse { text: "Ja.", outcome: DialogueOutcome::Continue(DialogueNodes::LepoSlišat)},  //tuki bi blo zabavn če se ti pokaže drgačn response če je tvoj anxiety too high
-                        DialogueResponse { text: "Ne, zelo se mi vrti, mislim, da bom omedlel.", outcome: DialogueOutcome::Continue(DialogueNodes::Poklicala)},
+                        DialogueResponse { text: "Ja.", outcome: DialogueOutcome::Continue(DialogueNodes::LepoSlišat), ..Default::default() },  //tuki bi blo zabavn če se ti pokaže drgačn response če je tvoj anxiety too high
+                        DialogueResponse { text: "Ne, zelo se mi vrti, mislim, da bom omedlel.", outcome: DialogueOutcome::Continue(DialogueNodes::Poklicala), ..Default::default() },
                     ],
                 },
             );
@@ -479,10 +1467,10 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
             map.insert(
                 DialogueNodes::LanASiVRedu,
                 DialogueNode {
-                    text: "Lan? A si v redu? Jaz sem Ema, tvoja prijateljica!",
+                    text: "{ime}? A si v redu? Jaz sem Ema, tvoja prijateljica!",
                     responses: vec![
-                        DialogueResponse { text: "Ah, seveda, saj res.", outcome: DialogueOutcome::Continue(DialogueNodes::UfSeDobro)}, 
-                        DialogueResponse { text: "Kaj? Prvič slišim zate.", outcome: DialogueOutcome::Continue(DialogueNodes::LanMarSiIzgubil)},
+                        DialogueResponse { text: "Ah, seveda, saj res.", outcome: DialogueOutcome::Continue(DialogueNodes::UfSeDobro), ..Default::default() }, 
+                        DialogueResponse { text: "Kaj? Prvič slišim zate.", outcome: DialogueOutcome::Continue(DialogueNodes::LanMarSiIzgubil), ..Default::default() },
                 ],
                 },
             );
@@ -490,10 +1478,10 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
             map.insert(
                 DialogueNodes::LanMarSiIzgubil,
                 DialogueNode {
-                    text: "Lan?? Mar si izgubil spomin? Ali veš, kdo si in kje si?",
+                    text: "{ime}?? Mar si izgubil spomin? Ali veš, kdo si in kje si?",
                     responses: vec![
-                        DialogueResponse { text: "Ja, jaz sem Lan in sem v Mafiji, vsega se spomnem normalno, samo tebe ne. Povej mi več o sebi.", outcome: DialogueOutcome::Continue(DialogueNodes::JazSemEma)},
-                        DialogueResponse { text: "V bistvu ne...", outcome: DialogueOutcome::Continue(DialogueNodes::LanResSi)},
+                        DialogueResponse { text: "Ja, jaz sem {ime} in sem v Mafiji, vsega se spomnem normalno, samo tebe ne. Povej mi več o sebi.", outcome: DialogueOutcome::Continue(DialogueNodes::JazSemEma), ..Default::default() },
+                        DialogueResponse { text: "V bistvu ne...", outcome: DialogueOutcome::Continue(DialogueNodes::LanResSi), ..Default::default() },
                     ],
                 },
             );
@@ -503,7 +1491,7 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Poklicala bom rešilca",
                     responses: vec![
-                        DialogueResponse { text: "Ok.", outcome: DialogueOutcome::EndGame}, 
+                        DialogueResponse { text: "Ok.", outcome: DialogueOutcome::EndGame(Ending::Rešilec), ..Default::default() }, 
                     ],
                 },
             );
@@ -513,9 +1501,9 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Jaz sem Ema, spoznala sva se na FMF, kjer sva sošolca že dve leti. Povsod sediva skupaj. Z Borom imamo tekaški klub. Skupaj delava projektno za Programiranje 2...",
                     responses: vec![
-                        DialogueResponse { text: "Ah, seveda, saj res.", outcome: DialogueOutcome::Continue(DialogueNodes::UfSeDobro)},  
-                        DialogueResponse { text: "To ni mogoče, spomnim se, da sem celo projektno za Programiranje 2 napisal sam.", outcome: DialogueOutcome::Continue(DialogueNodes::EjASiMeVBistvu)}, 
-                        DialogueResponse { text: "Kdo je Bor?", outcome: DialogueOutcome::Continue(DialogueNodes::LanResSi)},
+                        DialogueResponse { text: "Ah, seveda, saj res.", outcome: DialogueOutcome::Continue(DialogueNodes::UfSeDobro), ..Default::default() },  
+                        DialogueResponse { text: "To ni mogoče, spomnim se, da sem celo projektno za Programiranje 2 napisal sam.", outcome: DialogueOutcome::Continue(DialogueNodes::EjASiMeVBistvu), ..Default::default() }, 
+                        DialogueResponse { text: "Kdo je Bor?", outcome: DialogueOutcome::Continue(DialogueNodes::LanResSi), ..Default::default() },
                     ],
                 },
             );
@@ -523,9 +1511,9 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
             map.insert(
                 DialogueNodes::LanResSi,
                 DialogueNode {
-                    text: "Lan!! Res si izgubil spomin!! Poklicala bom rešilca.",
+                    text: "{ime}!! Res si izgubil spomin!! Poklicala bom rešilca.",
                     responses: vec![
-                        DialogueResponse { text: "Mogoče je tako res bolje.", outcome: DialogueOutcome::EndGame},  
+                        DialogueResponse { text: "Mogoče je tako res bolje.", outcome: DialogueOutcome::EndGame(Ending::Rešilec), ..Default::default() },  
                     ],
                 },
             );
@@ -535,8 +1523,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode { // some(15)
                     text: "Ej! A se me v bistvu spomneš, in me samo zafrkavaš, ker se ti zdi, da sem premalo naredila?",
                     responses: vec![
-                        DialogueResponse { text: "Ja.", outcome: DialogueOutcome::Continue(DialogueNodes::EjToPaNiRes)},  
-                        DialogueResponse { text: "Ne, res ne vem, kdo naj bi ti bila.", outcome: DialogueOutcome::Continue(DialogueNodes::LanResSi)},
+                        DialogueResponse { text: "Ja.", outcome: DialogueOutcome::Continue(DialogueNodes::EjToPaNiRes), ..Default::default() },  
+                        DialogueResponse { text: "Ne, res ne vem, kdo naj bi ti bila.", outcome: DialogueOutcome::Continue(DialogueNodes::LanResSi), ..Default::default() },
                     ],
                 },
             );
@@ -546,8 +1534,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode { // some(16)
                     text: "Ej!! To pa ni res!! Jaz sem naredila en commit na readme-ju!!",
                     responses: vec![
-                        DialogueResponse { text: "To mi nič ne pomeni.", outcome: DialogueOutcome::Continue(DialogueNodes::Oprosti),},  
-                        DialogueResponse { text: "Prav imaš. V bistvu si super soprogramerka.", outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLan)},
+                        DialogueResponse { text: "To mi nič ne pomeni.", outcome: DialogueOutcome::Continue(DialogueNodes::Oprosti), effects: vec![DialogueEffect::Affinity(-10)], ..Default::default() },  
+                        DialogueResponse { text: "Prav imaš. V bistvu si super soprogramerka.", outcome: DialogueOutcome::Continue(DialogueNodes::HvalaLan), effects: vec![DialogueEffect::Affinity(10)], ..Default::default() },
                     ],
                 },
             );
@@ -557,8 +1545,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode { // some(17)
                     text: "Lepo slišat! Kaj pa počenjaš tu?",
                     responses: vec![
-                        DialogueResponse { text: "Pijem kavo in hodim okrog.", outcome: DialogueOutcome::Continue(DialogueNodes::OhToRavno)},
-                        DialogueResponse { text: "V bistvu sem hotel iti stran od tebe.", outcome: DialogueOutcome::Continue(DialogueNodes::Ok3)},
+                        DialogueResponse { text: "Pijem kavo in hodim okrog.", outcome: DialogueOutcome::Continue(DialogueNodes::OhToRavno), ..Default::default() },
+                        DialogueResponse { text: "V bistvu sem hotel iti stran od tebe.", outcome: DialogueOutcome::Continue(DialogueNodes::Ok3), effects: vec![DialogueEffect::Affinity(-10)], ..Default::default() },
                     ],
                 },
             );
@@ -568,8 +1556,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Kaj počenjaš tu?",
                     responses: vec![
-                        DialogueResponse { text: "Pijem kavo in hodim okrog.", outcome: DialogueOutcome::Continue(DialogueNodes::OhToRavno)}, 
-                        DialogueResponse { text: "V bistvu sem hotel iti stran od tebe.", outcome: DialogueOutcome::Continue(DialogueNodes::Ok3)},
+                        DialogueResponse { text: "Pijem kavo in hodim okrog.", outcome: DialogueOutcome::Continue(DialogueNodes::OhToRavno), ..Default::default() }, 
+                        DialogueResponse { text: "V bistvu sem hotel iti stran od tebe.", outcome: DialogueOutcome::Continue(DialogueNodes::Ok3), effects: vec![DialogueEffect::Affinity(-10)], ..Default::default() },
                     ],
                 },
             );
@@ -579,8 +1567,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Ok :(((",
                     responses: vec![
-                        DialogueResponse { text: "Saj sem se samo hecal.", outcome: DialogueOutcome::Continue(DialogueNodes::AhSiMeŽerestrašil)},
-                        DialogueResponse { text: "Zdaj bom šel stran, ker te ne maram.", outcome: DialogueOutcome::EndDialogue},
+                        DialogueResponse { text: "Saj sem se samo hecal.", outcome: DialogueOutcome::Continue(DialogueNodes::AhSiMeŽerestrašil), effects: vec![DialogueEffect::Affinity(5)], ..Default::default() },
+                        DialogueResponse { text: "Zdaj bom šel stran, ker te ne maram.", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(-20)], ..Default::default() },
                     ],
                 },
             );
@@ -590,8 +1578,8 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Ah, si me že prestrašil. Boš prisedel?",
                     responses: vec![
-                        DialogueResponse { text: "Lahko, samo naj si grem najprej še po eno kavo.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva)},  
-                        DialogueResponse { text: "V bistvu moram zares nekam iti.", outcome: DialogueOutcome::Continue(DialogueNodes::OhToRavno)}, 
+                        DialogueResponse { text: "Lahko, samo naj si grem najprej še po eno kavo.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva), effects: vec![DialogueEffect::Affinity(5)], ..Default::default() },  
+                        DialogueResponse { text: "V bistvu moram zares nekam iti.", outcome: DialogueOutcome::Continue(DialogueNodes::OhToRavno), ..Default::default() }, 
                     ],
                 },
             );
@@ -601,7 +1589,7 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Ok, se vidiva!",
                     responses: vec![
-                        DialogueResponse { text: "Ciao.", outcome: DialogueOutcome::EndDialogue},  
+                        DialogueResponse { text: "Ciao.", outcome: DialogueOutcome::EndDialogue, ..Default::default() },  
                     ],
                 },
             );
@@ -611,18 +1599,157 @@ pub fn npc_dialogue(npc: NpcId) -> HashMap<DialogueNodes, DialogueNode> {
                 DialogueNode {
                     text: "Oh, to ravno počnem tudi jaz, samo da sedim, namesto stojim. Boš prisedel?",
                     responses: vec![
-                        DialogueResponse { text: "Lahko, samo naj si grem najprej še po eno kavo.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva)},  
-                        DialogueResponse { text: "V bistvu moram zares nekam iti.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva)}, 
+                        DialogueResponse { text: "Lahko, samo naj si grem najprej še po eno kavo.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva), effects: vec![DialogueEffect::Affinity(5)], ..Default::default() },  
+                        DialogueResponse { text: "V bistvu moram zares nekam iti.", outcome: DialogueOutcome::Continue(DialogueNodes::OkSeVidiva), ..Default::default() }, 
                     ],
                 },
             );
         }
 
-        _ => {}
+        NpcId::Lan => {     //his own lines for when you play as Ema
+            map.insert(
+                DialogueNodes::LanŽivjo,
+                DialogueNode {
+                    text: "{ime}! Spet v Mafiji namesto na predavanjih?",
+                    responses: vec![
+                        DialogueResponse { text: "Živjo, Lan! Samo še eno kavo, potem grem.", outcome: DialogueOutcome::Continue(DialogueNodes::LanKava), effects: vec![DialogueEffect::Affinity(5)], ..Default::default() },
+                        DialogueResponse { text: "Ne težit.", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(-10)], ..Default::default() },
+                        DialogueResponse { text: "Kdo si ti?", outcome: DialogueOutcome::Continue(DialogueNodes::LanKdoSi), ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanKava,
+                DialogueNode {
+                    text: "Ok. A si že kaj naredila za projektno?",
+                    responses: vec![
+                        DialogueResponse { text: "Ja, en commit na README!", outcome: DialogueOutcome::Continue(DialogueNodes::LanReadme), ..Default::default() },
+                        DialogueResponse { text: "Jutri zares začnem.", outcome: DialogueOutcome::Continue(DialogueNodes::LanJutri), ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanReadme,
+                DialogueNode {
+                    text: "To ni projektna, {ime}. To je README. Pojdi delat!!!",
+                    responses: vec![
+                        DialogueResponse { text: "Dobro, dobro, grem.", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(5), DialogueEffect::StartQuest(QuestId::Projektna)], ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanJutri,
+                DialogueNode {
+                    text: "To si rekla že včeraj. In predvčerajšnjim.",
+                    responses: vec![
+                        DialogueResponse { text: "Tokrat mislim resno.", outcome: DialogueOutcome::Continue(DialogueNodes::LanReadme), ..Default::default() },
+                        DialogueResponse { text: "Ti pa sploh nisi začel.", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(-5)], ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanKdoSi,
+                DialogueNode {
+                    text: "Ha ha. Zelo smešno. Jaz sem tisti, ki ti vsak dan nosi kavo.",
+                    responses: vec![
+                        DialogueResponse { text: "Aja, res. Hvala, Lan!", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(5)], ..Default::default() },
+                        DialogueResponse { text: "Ne spomnim se.", outcome: DialogueOutcome::EndDialogue, effects: vec![DialogueEffect::Affinity(-10)], ..Default::default() },
+                    ],
+                },
+            );
+
+            //greetings that depend on how much Lan likes you
+            map.insert(
+                DialogueNodes::LanVesel,
+                DialogueNode {
+                    text: "{ime}! Izgledaš, kot da rabiš kavo. Častim.",
+                    responses: vec![
+                        DialogueResponse {      //favor, once per day
+                            text: "Ti si najboljši!",
+                            outcome: DialogueOutcome::Continue(DialogueNodes::LanČasti),
+                            condition: Some(Condition::FavorReady(NpcId::Lan)),
+                            effects: vec![DialogueEffect::Treat(ShopItem::Kava)],
+                            ..Default::default()
+                        },
+                        DialogueResponse { text: "Ne, hvala. Kako gre projektna?", outcome: DialogueOutcome::Continue(DialogueNodes::LanKava), ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanČasti,
+                DialogueNode {
+                    text: "Vem. Zdaj pa hitro, predavanje se začne čez pet minut.",
+                    responses: vec![
+                        DialogueResponse { text: "Se vidiva!", outcome: DialogueOutcome::EndDialogue, ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanJezen,
+                DialogueNode {
+                    text: "Zdaj pa kar naenkrat rabiš mene?",
+                    responses: vec![
+                        DialogueResponse { text: "Oprosti, Lan. Bila sem pod stresom.", outcome: DialogueOutcome::Continue(DialogueNodes::LanPozabljeno), effects: vec![DialogueEffect::Affinity(15)], ..Default::default() },
+                        DialogueResponse { text: "Ne, samo mimo sem šla.", outcome: DialogueOutcome::EndDialogue, ..Default::default() },
+                    ],
+                },
+            );
+
+            map.insert(
+                DialogueNodes::LanPozabljeno,
+                DialogueNode {
+                    text: "Ok, pozabljeno. Ampak naslednjo kavo plačaš ti.",
+                    responses: vec![
+                        DialogueResponse { text: "Dogovorjeno.", outcome: DialogueOutcome::EndDialogue, ..Default::default() },
+                    ],
+                },
+            );
+        }
+
+        NpcId::Peteršič | NpcId::Različnik => oral_exam::professor_dialogue(npc, &mut map),     //professors live in oral_exam.rs, their dialogue is the exam
     }
 
     map
 }
 
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_nighter_waits_for_the_oral_exam_to_end() {
+        let mut game_state = GameState::offline((1200.0, 600.0));
+        game_state.screen = Screen::Playing;
+        let exam = Some(OralExam { professor: NpcId::Peteršič, score: 3 });
+        game_state.interaction_state = InteractionState::Dialogue { npc: NpcId::Peteršič, node: DialogueNodes::PeteršičBinomska, exam, selection: 0 };
+        game_state.player.attention = wake_up::ALL_NIGHTER_ATTENTION - 1;
+        game_state.clock.day += 1;
+
+        game_state.check_new_day();
+        game_state.check_new_day();
+        assert!(matches!(game_state.screen, Screen::Playing));
+        assert!(matches!(game_state.interaction_state, InteractionState::Dialogue { exam: Some(OralExam { score: 3, .. }), .. }));
+
+        game_state.interaction_state = InteractionState::None;      //the professor gave the grade
+        game_state.check_new_day();
+        assert!(matches!(game_state.screen, Screen::MiniGame(_)));
+    }
+
+    #[test]
+    fn talking_again_types_the_greeting_again() {
+        let mut game_state = GameState::offline((1200.0, 600.0));
+        let line = (NpcId::Ema, game_state.dialogue_start(NpcId::Ema));
+        game_state.typewriter.skip(line);       //read the whole greeting last time
+        game_state.interact(Interactable::Npc(NpcId::Ema));
+        assert!(matches!(game_state.interaction_state, InteractionState::Dialogue { npc: NpcId::Ema, .. }));
+        assert_eq!(game_state.typewriter.visible(line, "Živjo!"), "");
+    }
+}
@@ -0,0 +1,229 @@
+//tipke: the game asks for actions (MoveUp, Interact...) instead of raw keys, which key does what can be changed and is saved in localStorage
+
+use crate::models::storage;
+use crate::msg::Msg;
+use std::collections::{HashMap, HashSet};
+
+const STORAGE_KEY: &str = "fakslife_keys";
+pub const KEYS_PER_ACTION: usize = 2;       //primary and secondary key, ex. ArrowUp and W
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveUp,     //also moves the selection up in menus
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Interact,       //talk to npcs, open the menu of an object
+    Confirm,        //pick the selected option
+    Cancel,     //close whatever is open
+    Inventory,
+    Transcript,
+    QuestLog,
+}
+
+pub const ALL_ACTIONS: [Action; 10] = [
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::Interact,
+    Action::Confirm,
+    Action::Cancel,
+    Action::Inventory,
+    Action::Transcript,
+    Action::QuestLog,
+];
+
+impl Action {
+    fn id(&self) -> &'static str {      //what gets saved in localStorage, don't change these
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Interact => "interact",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::Inventory => "inventory",
+            Action::Transcript => "transcript",
+            Action::QuestLog => "quest_log",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Action> {
+        ALL_ACTIONS.iter().copied().find(|action| action.id() == id)
+    }
+
+    pub fn label(&self) -> &'static str {       //shown on the key bindings screen
+        match self {
+            Action::MoveUp => "Gor",
+            Action::MoveDown => "Dol",
+            Action::MoveLeft => "Levo",
+            Action::MoveRight => "Desno",
+            Action::Interact => "Interakcija",
+            Action::Confirm => "Potrdi",
+            Action::Cancel => "Prekliči",
+            Action::Inventory => "Nahrbtnik",
+            Action::Transcript => "Indeks",
+            Action::QuestLog => "Naloge",
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::MoveUp => &["ArrowUp", "w"],
+            Action::MoveDown => &["ArrowDown", "s"],
+            Action::MoveLeft => &["ArrowLeft", "a"],
+            Action::MoveRight => &["ArrowRight", "d"],
+            Action::Interact => &["f"],
+            Action::Confirm => &["Enter", "Space"],
+            Action::Cancel => &["Escape"],
+            Action::Inventory => &["i"],
+            Action::Transcript => &["t"],
+            Action::QuestLog => &["q"],
+        }
+    }
+}
+
+//the same key always has the same name: letters are lowercase (so Caps Lock and Shift don't matter) and the space bar is "Space"
+pub fn normalize(key: &str) -> String {
+    if key == " " {
+        return "Space".to_string();
+    }
+    if key.chars().count() == 1 {
+        return key.to_lowercase();
+    }
+    key.to_string()
+}
+
+pub fn key_label(key: &str) -> String {     //how a key is written on screen
+    match key {
+        "ArrowUp" => "↑".to_string(),
+        "ArrowDown" => "↓".to_string(),
+        "ArrowLeft" => "←".to_string(),
+        "ArrowRight" => "→".to_string(),
+        "Escape" => "Esc".to_string(),
+        _ => key.to_uppercase(),
+    }
+}
+
+pub struct KeyBindings {
+    keys: HashMap<Action, Vec<String>>,     //normalized keys, at most KEYS_PER_ACTION per action
+}
+
+impl KeyBindings {
+    pub fn default_bindings() -> Self {
+        let keys = ALL_ACTIONS
+            .iter()
+            .map(|action| (*action, action.default_keys().iter().map(|key| key.to_string()).collect()))
+            .collect();
+        KeyBindings { keys }
+    }
+
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY).map_or_else(KeyBindings::default_bindings, |saved| KeyBindings::from_text(&saved))
+    }
+
+    //saved as one line per action: "move_up ArrowUp w", actions that aren't saved keep their default keys
+    fn from_text(saved: &str) -> Self {
+        let mut bindings = KeyBindings::default_bindings();
+        for line in saved.lines() {
+            let mut parts = line.split_whitespace();
+            if let Some(action) = parts.next().and_then(Action::from_id) {
+                bindings.keys.insert(action, parts.take(KEYS_PER_ACTION).map(str::to_string).collect());
+            }
+        }
+        bindings
+    }
+
+    fn to_text(&self) -> String {
+        let lines: Vec<String> = ALL_ACTIONS
+            .iter()
+            .map(|action| format!("{} {}", action.id(), self.keys(*action).join(" ")))
+            .collect();
+        lines.join("\n")
+    }
+
+    fn save(&self) {
+        storage::save(STORAGE_KEY, &self.to_text());
+    }
+
+    pub fn keys(&self, action: Action) -> &[String] {
+        self.keys.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn label(&self, action: Action) -> String {     //first key of the action, for hints like "T = zapri"
+        self.keys(action).first().map(|key| key_label(key)).unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn actions(&self, key: &str) -> Vec<Action> {     //everything this key does (nothing if it isn't bound)
+        let key = normalize(key);
+        ALL_ACTIONS.iter().copied().filter(|action| self.keys(*action).contains(&key)).collect()
+    }
+
+    pub fn pressed(&self, msg: &Msg) -> Vec<Action> {     //actions of a key press or a controller button, same for everyone who reads input
+        match msg {
+            Msg::KeyDown(key) => self.actions(key),
+            Msg::Pressed(action) => vec![*action],
+            _ => vec![],
+        }
+    }
+
+    pub fn held(&self, pressed_keys: &HashSet<String>, action: Action) -> bool {        //pressed_keys are already normalized
+        self.keys(action).iter().any(|key| pressed_keys.contains(key))
+    }
+
+    //puts the key into the slot, if another action already used it, it loses it (one key never does two things)
+    pub fn bind(&mut self, action: Action, slot: usize, key: &str) {
+        let key = normalize(key);
+        for keys in self.keys.values_mut() {
+            keys.retain(|bound| *bound != key);
+        }
+        let keys = self.keys.entry(action).or_default();
+        if slot < keys.len() {
+            keys[slot] = key;
+        } else {
+            keys.push(key);
+        }
+        self.save();
+    }
+
+    pub fn reset(&mut self) {
+        *self = KeyBindings::default_bindings();
+        self.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_normalized() {
+        assert_eq!(normalize("W"), "w");
+        assert_eq!(normalize(" "), "Space");
+        assert_eq!(normalize("ArrowUp"), "ArrowUp");
+    }
+
+    #[test]
+    fn saved_bindings_load_back() {
+        let bindings = KeyBindings::from_text("move_up u\ninteract e Enter\nnonsense x");
+        assert_eq!(bindings.keys(Action::MoveUp), ["u"]);
+        assert_eq!(bindings.keys(Action::Interact), ["e", "Enter"]);
+        assert_eq!(bindings.keys(Action::MoveDown), ["ArrowDown", "s"]);       //not saved, so the default
+        assert_eq!(bindings.actions("U"), vec![Action::MoveUp]);
+
+        let again = KeyBindings::from_text(&bindings.to_text());
+        for action in ALL_ACTIONS {
+            assert_eq!(again.keys(action), bindings.keys(action));
+        }
+    }
+
+    #[test]
+    fn pressed_reads_keys_and_buttons() {
+        let bindings = KeyBindings::default_bindings();
+        assert_eq!(bindings.pressed(&Msg::KeyDown("Escape".to_string())), vec![Action::Cancel]);
+        assert_eq!(bindings.pressed(&Msg::Pressed(Action::Confirm)), vec![Action::Confirm]);
+        assert!(bindings.pressed(&Msg::Tick).is_empty());
+    }
+}
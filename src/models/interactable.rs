@@ -2,6 +2,7 @@
 pub enum Objects {     //all possible interactable objects we have
     Counter,
     Door,
+    ExamDesk,       //katedra in 2. štuk where you write exams
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -13,7 +14,47 @@ pub enum Interactable {     //differentiating between objects and npcs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NpcId {    //a least for now we'll keep it inside this file and then maybe we can move it into a seperate one later
     Ema,
+    Lan,        //only walks around when you play as Ema
+    Peteršič,       //profesor za STOP, sedi v kabinetu
+    Različnik,      //profesor za fiziko
     //Bor,
     //Indija,
     //Matija,
 }
+
+impl NpcId {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NpcId::Ema => "Ema",
+            NpcId::Lan => "Lan",
+            NpcId::Peteršič => "Peteršič",
+            NpcId::Različnik => "Različnik",
+        }
+    }
+
+    pub fn portrait(&self) -> &'static str {        //no separate portraits yet, the dialogue box shows the top of the sprite
+        match self {
+            NpcId::Ema => "/static/characters/ema_naravnost_2.png",
+            NpcId::Lan => "/static/characters/lan_naravnost_4x.png",
+            NpcId::Peteršič | NpcId::Različnik => "/static/characters/prijatelj.png",
+        }
+    }
+
+    pub fn ticks_per_letter(&self) -> u32 {     //how fast they talk in the dialogue box, 1 = a letter every tick
+        match self {
+            NpcId::Ema => 1,        //she always talks like she's late for something
+            NpcId::Lan => 2,
+            NpcId::Peteršič => 3,       //every word is important on an exam
+            NpcId::Različnik => 2,
+        }
+    }
+
+    pub fn voice(&self) -> f32 {        //pitch of the blips while they talk, in Hz
+        match self {
+            NpcId::Ema => 660.0,
+            NpcId::Lan => 440.0,
+            NpcId::Peteršič => 196.0,
+            NpcId::Različnik => 294.0,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//everything the player carries around: vouchers, books, phone...
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    BonZaTortiljo,      //študentski bon, you can only use it at the counter
+    Knjiga,     //library book
+    Telefon,
+    ListSFormulami,     //formula sheet
+}
+
+pub struct ItemEffect {     //what happens when you use an item from the inventory
+    pub anxiety: i32,       //negative calms you down
+    pub consumed: bool,     //whether one piece disappears after using it
+}
+
+impl ItemKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ItemKind::BonZaTortiljo => "Bon za tortiljo",
+            ItemKind::Knjiga => "Knjiga iz knjižnice",
+            ItemKind::Telefon => "Telefon",
+            ItemKind::ListSFormulami => "List s formulami",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ItemKind::BonZaTortiljo => "Porabiš ga lahko pri pultu v Mafiji.",
+            ItemKind::Knjiga => "Učenje ti dvigne anksioznost. Ne pozabi je vrniti.",
+            ItemKind::Telefon => "Malo anime brainrota te pomiri.",
+            ItemKind::ListSFormulami => "Ko ga pogledaš, se malo pomiriš.",
+        }
+    }
+
+    pub fn icon(&self) -> Option<&'static str> {        //only some items have their own picture for now
+        match self {
+            ItemKind::BonZaTortiljo => Some("/static/interactive_items/tortila.png"),
+            _ => None,
+        }
+    }
+
+    pub fn max_stack(&self) -> u32 {        //how many of the same item fit into one slot
+        match self {
+            ItemKind::BonZaTortiljo => 10,
+            ItemKind::Knjiga => 5,
+            ItemKind::Telefon | ItemKind::ListSFormulami => 1,
+        }
+    }
+
+    pub fn use_effect(&self) -> Option<ItemEffect> {        //None means you can't use it from the inventory
+        match self {
+            ItemKind::BonZaTortiljo => None,
+            ItemKind::Knjiga => Some(ItemEffect { anxiety: 3, consumed: false }),
+            ItemKind::Telefon => Some(ItemEffect { anxiety: -5, consumed: false }),
+            ItemKind::ListSFormulami => Some(ItemEffect { anxiety: -3, consumed: false }),
+        }
+    }
+}
+
+pub struct InventorySlot {
+    pub kind: ItemKind,
+    pub count: u32,
+}
+
+pub struct Inventory {
+    pub slots: Vec<InventorySlot>,      //in the order you got the items
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Inventory { slots: vec![] }
+    }
+
+    pub fn starting() -> Self {     //what you have in your backpack at the start of a new game
+        let mut inventory = Inventory::new();
+        inventory.add(ItemKind::Telefon, 1);
+        inventory.add(ItemKind::Knjiga, 1);
+        inventory.add(ItemKind::BonZaTortiljo, 2);
+        inventory
+    }
+
+    pub fn count(&self, kind: ItemKind) -> u32 {
+        self.slots.iter().find(|slot| slot.kind == kind).map_or(0, |slot| slot.count)
+    }
+
+    pub fn has(&self, kind: ItemKind) -> bool {
+        self.count(kind) > 0
+    }
+
+    //returns how many actually fit in (the rest is lost, ex. you can't carry two phones)
+    pub fn add(&mut self, kind: ItemKind, amount: u32) -> u32 {
+        let max = kind.max_stack();
+        match self.slots.iter_mut().find(|slot| slot.kind == kind) {
+            Some(slot) => {
+                let added = amount.min(max - slot.count);
+                slot.count += added;
+                added
+            }
+            None => {
+                let added = amount.min(max);
+                if added > 0 {
+                    self.slots.push(InventorySlot { kind, count: added });
+                }
+                added
+            }
+        }
+    }
+
+    //only removes if you have enough, returns whether it did
+    pub fn remove(&mut self, kind: ItemKind, amount: u32) -> bool {
+        let Some(index) = self.slots.iter().position(|slot| slot.kind == kind) else {
+            return false;
+        };
+        if self.slots[index].count < amount {
+            return false;
+        }
+        self.slots[index].count -= amount;
+        if self.slots[index].count == 0 {
+            self.slots.remove(index);       //empty slots disappear
+        }
+        true
+    }
+}
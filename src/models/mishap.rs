@@ -0,0 +1,242 @@
+//manjše nevšečnosti: tripping, the WC door, a professor who's late... they're defined in data/mishaps so writers can add new ones
+
+use crate::models::gamestate::Condition;
+use crate::models::inventory::ItemKind;
+use crate::models::room::{Room, ALL_ROOMS};
+use crate::models::clock::MINUTES_PER_DAY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Random,     //rolled every in-game minute while you walk around
+    Morning,        //a new day starts
+    EnterRoom,      //you walk through a door
+    WrittenExam,        //right before the exam sheet appears
+    OralExam,       //right before the professor asks the first question
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MishapEffect {
+    Anxiety(i32),
+    Attention(i32),
+    Money(i32),     //negative = you lose money
+    Time(u32),      //minutes that pass
+    Lose(ItemKind),
+    Get(ItemKind),
+}
+
+impl MishapEffect {
+    pub fn label(&self) -> String {     //shown on the last page of the popup (goes through GameState::say)
+        match self {
+            MishapEffect::Anxiety(amount) => format!("Anksioznost {:+}", amount),
+            MishapEffect::Attention(amount) => format!("Pozornost {:+}", amount),
+            MishapEffect::Money(amount) => format!("{:+} €", amount),
+            MishapEffect::Time(minutes) => format!("+{} min", minutes),
+            MishapEffect::Lose(kind) => format!("[Izgubil|Izgubila] si: {}", kind.name()),
+            MishapEffect::Get(kind) => format!("[Dobil|Dobila] si: {}", kind.name()),
+        }
+    }
+}
+
+pub struct Mishap {
+    pub title: String,
+    pub pages: Vec<String>,     //popup text, one page after another
+    pub trigger: Trigger,
+    pub chance: u32,        //percent: per in-game hour for random mishaps, per occasion for the rest
+    pub room: Option<Room>,     //None = anywhere
+    pub from: u32,      //time window in minutes after midnight
+    pub to: u32,
+    pub conditions: Vec<Condition>,
+    pub effects: Vec<MishapEffect>,
+}
+
+impl Mishap {
+    pub fn in_time_window(&self, minutes: u32) -> bool {
+        self.from <= minutes && minutes < self.to
+    }
+
+    pub fn probability(&self) -> f64 {      //for a single roll
+        let chance = self.chance as f64 / 100.0;
+        match self.trigger {
+            Trigger::Random => chance / 60.0,       //random ones get rolled once per in-game minute
+            _ => chance,
+        }
+    }
+}
+
+pub struct MishapRecord {       //a mishap that already happened this run
+    pub day: u32,
+    pub minutes: u32,
+    pub index: usize,       //index into GameState::mishaps
+}
+
+pub struct ActiveMishap {       //the popup that's open right now
+    pub index: usize,
+    pub page: usize,
+}
+
+pub fn all_mishaps() -> Vec<Mishap> {       //baked into the wasm like the exam questions
+    parse_mishaps(include_str!("../../data/mishaps/mishaps.txt"))
+}
+
+fn item_by_id(id: &str) -> Option<ItemKind> {
+    match id {
+        "bon" => Some(ItemKind::BonZaTortiljo),
+        "knjiga" => Some(ItemKind::Knjiga),
+        "telefon" => Some(ItemKind::Telefon),
+        "formule" => Some(ItemKind::ListSFormulami),
+        _ => None,
+    }
+}
+
+fn parse_trigger(text: &str) -> Option<(Trigger, u32)> {
+    let mut parts = text.split_whitespace();
+    let trigger = match parts.next()? {
+        "random" => Trigger::Random,
+        "morning" => Trigger::Morning,
+        "room" => Trigger::EnterRoom,
+        "written" => Trigger::WrittenExam,
+        "oral" => Trigger::OralExam,
+        _ => return None,
+    };
+    let chance = match parts.next() {
+        Some(chance) => chance.parse().ok()?,
+        None => 100,
+    };
+    Some((trigger, chance))
+}
+
+fn parse_time(text: &str) -> Option<u32> {     //"8:30" -> 510
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let minutes = hours.parse::<u32>().ok()? * 60 + minutes.parse::<u32>().ok()?;
+    (minutes <= MINUTES_PER_DAY).then_some(minutes)
+}
+
+fn parse_condition(text: &str) -> Option<Condition> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    match parts.as_slice() {
+        ["has", item] => Some(Condition::Has(item_by_id(item)?)),
+        ["missing", item] => Some(Condition::Missing(item_by_id(item)?)),
+        ["anxiety", ">=", limit] => Some(Condition::AnxietyAtLeast(limit.parse().ok()?)),
+        ["anxiety", "<", limit] => Some(Condition::AnxietyBelow(limit.parse().ok()?)),
+        ["attention", ">=", limit] => Some(Condition::AttentionAtLeast(limit.parse().ok()?)),
+        ["attention", "<", limit] => Some(Condition::AttentionBelow(limit.parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn parse_effect(text: &str) -> Option<MishapEffect> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    match parts.as_slice() {
+        ["anxiety", amount] => Some(MishapEffect::Anxiety(amount.parse().ok()?)),
+        ["attention", amount] => Some(MishapEffect::Attention(amount.parse().ok()?)),
+        ["money", amount] => Some(MishapEffect::Money(amount.parse().ok()?)),
+        ["time", minutes] => Some(MishapEffect::Time(minutes.parse().ok()?)),
+        ["lose", item] => Some(MishapEffect::Lose(item_by_id(item)?)),
+        ["get", item] => Some(MishapEffect::Get(item_by_id(item)?)),
+        _ => None,
+    }
+}
+
+//one line of a mishap, returns None if the line can't be read
+fn parse_line(mishap: &mut Mishap, has_trigger: &mut bool, line: &str) -> Option<()> {
+    if let Some(rest) = line.strip_prefix('>') {
+        mishap.pages.push(rest.trim().to_string());
+    } else if let Some(rest) = line.strip_prefix('+') {
+        mishap.effects.push(parse_effect(rest)?);
+    } else if let Some(rest) = line.strip_prefix("when ") {
+        (mishap.trigger, mishap.chance) = parse_trigger(rest)?;
+        *has_trigger = true;
+    } else if let Some(rest) = line.strip_prefix("where ") {
+        let room = ALL_ROOMS.iter().find(|room| room.name().to_lowercase() == rest.trim().to_lowercase())?;
+        mishap.room = Some(*room);
+    } else if let Some(rest) = line.strip_prefix("time ") {
+        let (from, to) = rest.split_once('-')?;
+        mishap.from = parse_time(from)?;
+        mishap.to = parse_time(to)?;
+    } else if let Some(rest) = line.strip_prefix("if ") {
+        mishap.conditions.push(parse_condition(rest)?);
+    } else {
+        return None;
+    }
+    Some(())
+}
+
+//format is described in data/mishaps/README.txt, mishaps with a broken line, no trigger or no text are skipped
+pub fn parse_mishaps(source: &str) -> Vec<Mishap> {
+    let mut mishaps = vec![];
+    let mut current: Option<Mishap> = None;
+    let mut has_trigger = false;
+    let mut broken = false;
+
+    let mut flush = |current: &mut Option<Mishap>, has_trigger: bool, broken: bool| {
+        if let Some(mishap) = current.take() {
+            if has_trigger && !broken && !mishap.pages.is_empty() {
+                mishaps.push(mishap);
+            }
+        }
+    };
+
+    for line in source.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('!') {
+            flush(&mut current, has_trigger, broken);
+            current = Some(Mishap {
+                title: rest.trim().to_string(),
+                pages: vec![],
+                trigger: Trigger::Random,
+                chance: 100,
+                room: None,
+                from: 0,
+                to: MINUTES_PER_DAY,
+                conditions: vec![],
+                effects: vec![],
+            });
+            has_trigger = false;
+            broken = false;
+        } else if let Some(mishap) = &mut current {
+            if parse_line(mishap, &mut has_trigger, line).is_none() {
+                broken = true;
+            }
+        }
+    }
+    flush(&mut current, has_trigger, broken);
+
+    mishaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_mishap() {
+        let mishaps = parse_mishaps(
+            "# komentar\n! Profesor zamuja\nwhen oral 50\nwhere Kabinet\ntime 8:00-12:30\nif anxiety >= 40\nif has bon\n> Čakaš.\n> Še vedno čakaš.\n+ anxiety 5\n+ time 30\n",
+        );
+        assert_eq!(mishaps.len(), 1);
+        let mishap = &mishaps[0];
+        assert_eq!(mishap.title, "Profesor zamuja");
+        assert_eq!((mishap.trigger, mishap.chance), (Trigger::OralExam, 50));
+        assert_eq!(mishap.room, Some(Room::Kabinet));
+        assert_eq!((mishap.from, mishap.to), (8 * 60, 12 * 60 + 30));
+        assert!(matches!(mishap.conditions[..], [Condition::AnxietyAtLeast(40), Condition::Has(ItemKind::BonZaTortiljo)]));
+        assert_eq!(mishap.pages.len(), 2);
+        assert_eq!(mishap.effects, vec![MishapEffect::Anxiety(5), MishapEffect::Time(30)]);
+    }
+
+    #[test]
+    fn skips_broken_mishaps() {
+        let mishaps = parse_mishaps("! brez sprožilca\n> tekst\n\n! brez teksta\nwhen random\n\n! čudna vrstica\nwhen morning\n> tekst\n+ nekaj 5\n\n! ok\nwhen morning\n> tekst\n");
+        assert_eq!(mishaps.len(), 1);
+        assert_eq!(mishaps[0].title, "ok");
+        assert_eq!(mishaps[0].chance, 100);
+    }
+
+    #[test]
+    fn bundled_mishaps_all_parse() {
+        let count = include_str!("../../data/mishaps/mishaps.txt").lines().filter(|line| line.starts_with('!')).count();
+        assert_eq!(all_mishaps().len(), count);
+    }
+}
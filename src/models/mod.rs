@@ -3,3 +3,29 @@
 pub mod player;
 pub mod gamestate;
 pub mod interactable;
+pub mod clock;
+pub mod room;
+pub mod npc;
+pub mod pathfinding;
+pub mod inventory;
+pub mod shop;
+pub mod economy;
+pub mod rng;
+pub mod exam;
+pub mod oral_exam;
+pub mod transcript;
+pub mod mishap;
+pub mod wake_up;
+pub mod affinity;
+pub mod quest;
+pub mod character;
+pub mod storage;
+pub mod ending;
+pub mod achievement;
+pub mod input;
+pub mod gamepad;
+pub mod touch;
+pub mod typewriter;
+pub mod save;
+pub mod settings;
+pub mod audio;
@@ -0,0 +1,139 @@
+//npcs that walk around on their own, following a daily schedule tied to the in-game clock
+
+use crate::models::interactable::NpcId;
+use crate::models::pathfinding::find_path;
+use crate::models::player::Smer;
+use crate::models::room::Room;
+
+pub struct ScheduleEntry {
+    pub from: u32,      //from which minute of the day (8:00 = 480) this entry is active
+    pub room: Room,     //where the npc wants to be
+    pub x: f64,
+    pub y: f64,
+}
+
+pub struct Npc {
+    pub id: NpcId,
+    pub room: Room,     //room the npc is currently in (not where it's going)
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub smer: Smer,
+    pub speed: f64,
+    pub path: Vec<(f64, f64)>,      //waypoints we still have to walk to, first one is the next
+    pub destination: Option<(Room, f64, f64)>,      //which schedule target we already planned a path for
+    pub stuck: bool,        //find_path found no way there, so we wait for the next schedule entry instead
+    pub schedule: Vec<ScheduleEntry>,       //sorted by `from`
+}
+
+impl Npc {
+    pub fn new(id: NpcId) -> Self {
+        let image_scale = 1.5;
+        match id {
+            NpcId::Ema | NpcId::Lan => {      //whoever you're not playing as keeps the same day in Mafija
+                let schedule = vec![
+                    ScheduleEntry { from: 8 * 60, room: Room::Mafija, x: 530., y: 450. },      //zjutraj sedi v Mafiji
+                    ScheduleEntry { from: 12 * 60, room: Room::DrugiŠtuk, x: 560., y: 380. },      //opoldne gre na predavanje
+                    ScheduleEntry { from: 15 * 60, room: Room::Mafija, x: 950., y: 250. },      //popoldne spet kava
+                ];
+                let first = &schedule[0];
+                Npc {
+                    id,
+                    room: first.room,
+                    x: first.x,
+                    y: first.y,
+                    width: (if id == NpcId::Ema { 24.0 } else { 20.0 }) * image_scale,      //og dimenzije so 24 x 62 (Lan 20 x 62)
+                    height: 62.0 * image_scale,
+                    smer: Smer::Stoji,
+                    speed: 2.5,     //a bit slower than the player
+                    path: vec![],
+                    destination: None,
+                    stuck: false,
+                    schedule,
+                }
+            }
+            NpcId::Peteršič | NpcId::Različnik => {     //profesorja ne hodita nikamor, cel dan sta v kabinetu
+                let (x, y) = if id == NpcId::Peteršič { (520., 100.) } else { (640., 100.) };
+                Npc {
+                    id,
+                    room: Room::Kabinet,
+                    x,
+                    y,
+                    width: 40.0 * image_scale,
+                    height: 62.0 * image_scale,
+                    smer: Smer::Stoji,
+                    speed: 2.0,
+                    path: vec![],
+                    destination: None,
+                    stuck: false,
+                    schedule: vec![ScheduleEntry { from: 0, room: Room::Kabinet, x, y }],
+                }
+            }
+        }
+    }
+
+    pub fn sprite(&self) -> &'static str {
+        match self.id {
+            NpcId::Ema => "/static/characters/ema_naravnost_2.png",
+            NpcId::Lan => "/static/characters/lan_naravnost_4x.png",
+            NpcId::Peteršič | NpcId::Različnik => "/static/characters/prijatelj.png",     //placeholder, both professors share it for now
+        }
+    }
+
+    //the entry whose time has come most recently, before the first entry of the day we're still on yesterday's last one
+    pub fn current_entry(&self, minutes: u32) -> &ScheduleEntry {
+        self.schedule
+            .iter()
+            .rev()
+            .find(|entry| entry.from <= minutes)
+            .unwrap_or_else(|| self.schedule.last().unwrap())
+    }
+
+    //plans a path to the destination, or to the door if the destination is in another room
+    pub fn plan_path(&mut self, world_width: f64, world_height: f64) {
+        let Some((room, x, y)) = self.destination else {
+            self.path.clear();
+            return;
+        };
+        let goal = if room == self.room { (x, y) } else { self.room.entrance() };
+        match find_path(&self.room.walls(), world_width, world_height, (self.x, self.y), goal, self.width, self.height) {
+            Some(path) => {
+                self.path = path;
+                self.stuck = false;
+            }
+            None => {       //if there is no way through we just stay where we are
+                self.path.clear();
+                self.stuck = true;
+            }
+        }
+    }
+
+    //walks one tick worth of distance towards the next waypoint
+    pub fn step(&mut self) {
+        let Some(&(tx, ty)) = self.path.first() else {
+            self.smer = Smer::Stoji;
+            return;
+        };
+        let dx = tx - self.x;
+        let dy = ty - self.y;
+        let dist = (dx * dx + dy * dy).sqrt();
+
+        if dist <= self.speed {
+            self.x = tx;
+            self.y = ty;
+            self.path.remove(0);
+        } else {
+            self.x += dx / dist * self.speed;
+            self.y += dy / dist * self.speed;
+        }
+
+        self.smer = if dx < 0.0 {
+            Smer::Levo
+        } else if dx > 0.0 {
+            Smer::Desno
+        } else {
+            Smer::Stoji
+        };
+    }
+}
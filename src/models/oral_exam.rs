@@ -0,0 +1,293 @@
+//ustni izpiti: professors are npcs whose dialogue is an exam, every answer gives points and some answers are a gamble
+
+use crate::models::exam::Subject;
+use crate::models::gamestate::{Condition, DialogueNode, DialogueNodes, DialogueOutcome, DialogueResponse};
+use crate::models::interactable::NpcId;
+use std::collections::HashMap;
+
+pub struct Professor {
+    pub name: &'static str,
+    pub subject: Subject,
+    pub pass_score: i32,        //how many points you need to pass
+    pub anxiety_weight: f64,        //how much your anxiety lowers your chances with this professor
+    pub attention_weight: f64,      //how much your attention helps
+}
+
+pub fn professor(npc: NpcId) -> Option<Professor> {
+    match npc {
+        NpcId::Peteršič => Some(Professor {     //strog, hitra vprašanja, zelo opazi živčnost
+            name: "prof. Pavel Peteršič",
+            subject: Subject::Stop,
+            pass_score: 4,
+            anxiety_weight: 0.6,
+            attention_weight: 0.2,
+        }),
+        NpcId::Različnik => Some(Professor {        //računske naloge s trikom, nagradi zbranost
+            name: "prof. Tomaž Različnik",
+            subject: Subject::Fizika,
+            pass_score: 3,
+            anxiety_weight: 0.2,
+            attention_weight: 0.6,
+        }),
+        _ => None,
+    }
+}
+
+impl Professor {
+    pub fn percent(&self, score: i32) -> u32 {     //exactly the pass score is 50 %, same line as the written exams
+        (score * 50 / self.pass_score).clamp(0, 100) as u32
+    }
+
+    pub fn passes(&self, score: i32) -> bool {
+        score >= self.pass_score
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct OralExam {       //the scoring track that runs next to the dialogue
+    pub professor: NpcId,
+    pub score: i32,
+}
+
+//success chance in percent for a gamble, from the base chance, the meters and the professor's personality
+pub fn gamble_chance(base: i32, uses_meters: bool, professor: Option<&Professor>, attention_ratio: f64, anxiety_ratio: f64) -> i32 {
+    if !uses_meters {
+        return base;
+    }
+    let (attention_weight, anxiety_weight) = professor.map_or((0.3, 0.3), |p| (p.attention_weight, p.anxiety_weight));
+    let chance = base as f64 + (attention_ratio - 0.5) * attention_weight * 100.0 - anxiety_ratio * anxiety_weight * 100.0;
+    (chance.round() as i32).clamp(5, 95)
+}
+
+fn answer(text: &'static str, next: DialogueNodes, score: i32) -> DialogueResponse {
+    DialogueResponse { text, outcome: DialogueOutcome::Continue(next), score, ..Default::default() }
+}
+
+fn gamble(text: &'static str, chance: i32, uses_meters: bool, success: DialogueNodes, failure: DialogueNodes) -> DialogueResponse {
+    DialogueResponse { text, outcome: DialogueOutcome::Gamble { chance, uses_meters, success, failure }, ..Default::default() }
+}
+
+fn node(text: &'static str, responses: Vec<DialogueResponse>) -> DialogueNode {
+    DialogueNode { text, responses }
+}
+
+pub fn professor_dialogue(npc: NpcId, map: &mut HashMap<DialogueNodes, DialogueNode>) {
+    match npc {
+        NpcId::Peteršič => {
+            map.insert(DialogueNodes::PeteršičZačetek, node(
+                "Dober dan. Ste prišli na ustni izpit iz STOP-a? Imam samo pet minut.",
+                vec![
+                    DialogueResponse {
+                        text: "Ja, [pripravljen|pripravljena] sem.",
+                        outcome: DialogueOutcome::StartOralExam(DialogueNodes::PeteršičPogojna),
+                        condition: Some(Condition::ReadyForOral(Subject::Stop)),
+                        ..Default::default()
+                    },
+                    DialogueResponse {      //no written part (or already passed, or not your year) = no oral
+                        text: "Ja... ampak pisnega še nimam.",
+                        outcome: DialogueOutcome::Continue(DialogueNodes::PeteršičNajprejPisni),
+                        condition: Some(Condition::NotReadyForOral(Subject::Stop)),
+                        ..Default::default()
+                    },
+                    DialogueResponse { text: "Ne, samo mimo sem [šel|šla].", outcome: DialogueOutcome::EndDialogue, ..Default::default() },
+                ],
+            ));
+            map.insert(DialogueNodes::PeteršičNajprejPisni, node(
+                "Na ustni pridete, ko imate opravljen pisni del in ste vpisani v pravi letnik. Naslednji!",
+                vec![DialogueResponse { text: "(odideš)", outcome: DialogueOutcome::EndDialogue, ..Default::default() }],
+            ));
+            map.insert(DialogueNodes::PeteršičPogojna, node(
+                "Definirajte pogojno verjetnost. Hitro.",
+                vec![
+                    DialogueResponse {      //the clean definition only comes out if you're calm enough
+                        text: "P(A | B) = P(A ∩ B) / P(B), če je P(B) > 0.",
+                        outcome: DialogueOutcome::Continue(DialogueNodes::PeteršičBinomska),
+                        condition: Some(Condition::AnxietyBelow(60)),
+                        score: 2,
+                        ..Default::default()
+                    },
+                    answer("To je verjetnost, da se nekaj zgodi pod pogojem... nekega pogoja.", DialogueNodes::PeteršičBinomska, 0),
+                    gamble("Lahko to razložim na primeru s kockami?", 55, true, DialogueNodes::PeteršičKockeDa, DialogueNodes::PeteršičKockeNe),
+                ],
+            ));
+            map.insert(DialogueNodes::PeteršičKockeDa, node(
+                "Hm. Primer je pravilen. Nadaljujmo.",
+                vec![answer("(oddahneš si)", DialogueNodes::PeteršičBinomska, 2)],
+            ));
+            map.insert(DialogueNodes::PeteršičKockeNe, node(
+                "Kocke imajo šest strani, ne sedem. Naslednje vprašanje.",
+                vec![answer("(pogoltneš slino)", DialogueNodes::PeteršičBinomska, -1)],
+            ));
+            map.insert(DialogueNodes::PeteršičBinomska, node(
+                "Pričakovana vrednost binomske porazdelitve?",
+                vec![
+                    answer("n · p", DialogueNodes::PeteršičNeodvisnost, 2),
+                    answer("n · p · (1 - p)", DialogueNodes::PeteršičNeodvisnost, 0),
+                    gamble("Ugibam... n / p?", 15, false, DialogueNodes::PeteršičUgibDa, DialogueNodes::PeteršičUgibNe),
+                ],
+            ));
+            map.insert(DialogueNodes::PeteršičUgibDa, node(
+                "Narobe, ampak vsaj hitro. Cenim hitrost.",
+                vec![answer("(ne veš, ali je to pohvala)", DialogueNodes::PeteršičNeodvisnost, 1)],
+            ));
+            map.insert(DialogueNodes::PeteršičUgibNe, node(
+                "Ne ugibajte na mojem izpitu.",
+                vec![answer("Oprostite.", DialogueNodes::PeteršičNeodvisnost, -1)],
+            ));
+            map.insert(DialogueNodes::PeteršičNeodvisnost, node(
+                "Kdaj sta dogodka neodvisna?",
+                vec![
+                    answer("Ko velja P(A ∩ B) = P(A) · P(B).", DialogueNodes::PeteršičKonec, 2),
+                    answer("Ko se ne zgodita hkrati.", DialogueNodes::PeteršičKonec, -1),
+                    DialogueResponse {      //sometimes the mind goes blank
+                        text: "Ko... ko... (pozabiš, kaj si [hotel|hotela] reči)",
+                        outcome: DialogueOutcome::Continue(DialogueNodes::PeteršičKonec),
+                        condition: Some(Condition::AnxietyAtLeast(60)),
+                        ..Default::default()
+                    },
+                ],
+            ));
+            map.insert(DialogueNodes::PeteršičKonec, node(
+                "Dobro. To je bilo to. Oceno boste videli v sistemu.",
+                vec![DialogueResponse { text: "(čakaš na oceno)", outcome: DialogueOutcome::FinishOralExam, ..Default::default() }],
+            ));
+        }
+
+        NpcId::Različnik => {
+            map.insert(DialogueNodes::RazličnikZačetek, node(
+                "Aha, {ime}, še en pogumen študent! Bova malo računala iz fizike?",
+                vec![
+                    DialogueResponse {
+                        text: "Ja, pa dajmo.",
+                        outcome: DialogueOutcome::StartOralExam(DialogueNodes::RazličnikKrog),
+                        condition: Some(Condition::ReadyForOral(Subject::Fizika)),
+                        ..Default::default()
+                    },
+                    DialogueResponse {
+                        text: "[Rad|Rada] bi, ampak pisnega še nisem [naredil|naredila].",
+                        outcome: DialogueOutcome::Continue(DialogueNodes::RazličnikNajprejPisni),
+                        condition: Some(Condition::NotReadyForOral(Subject::Fizika)),
+                        ..Default::default()
+                    },
+                    DialogueResponse { text: "Mogoče raje naslednji rok.", outcome: DialogueOutcome::EndDialogue, ..Default::default() },
+                ],
+            ));
+            map.insert(DialogueNodes::RazličnikNajprejPisni, node(
+                "Ah, nič hudega! Najprej pisni, potem pa se vidiva tukaj. Pa srečno!",
+                vec![DialogueResponse { text: "Hvala!", outcome: DialogueOutcome::EndDialogue, ..Default::default() }],
+            ));
+            map.insert(DialogueNodes::RazličnikKrog, node(
+                "Kolo s polmerom en meter se enkrat zavrti. Koliko poti naredi?",
+                vec![
+                    gamble("Obseg je 2πr, torej približno...", 75, true, DialogueNodes::RazličnikPiTri, DialogueNodes::RazličnikPiPet),
+                    answer("En meter, saj ima polmer en meter.", DialogueNodes::RazličnikKlanec, -1),
+                ],
+            ));
+            map.insert(DialogueNodes::RazličnikPiTri, node(
+                "6,28 metra, odlično!",
+                vec![answer("(nasmehneš se)", DialogueNodes::RazličnikKlanec, 2)],
+            ));
+            map.insert(DialogueNodes::RazličnikPiPet, node(
+                "Deset metrov?! Ste π zaokrožili na 5 namesto na 3?",
+                vec![
+                    answer("...ja.", DialogueNodes::RazličnikKlanec, -1),
+                    gamble("To je inženirski približek!", 30, true, DialogueNodes::RazličnikInženir, DialogueNodes::RazličnikKlanec),
+                ],
+            ));
+            map.insert(DialogueNodes::RazličnikInženir, node(
+                "Haha, dober! Tega si bom zapomnil.",
+                vec![answer("(nisi [prepričan|prepričana], ali je to dobro)", DialogueNodes::RazličnikKlanec, 1)],
+            ));
+            map.insert(DialogueNodes::RazličnikKlanec, node(
+                "Klada drsi po klancu s trenjem. Katero spremenljivko lahko zanemariš?",
+                vec![
+                    answer("Nobene, vse so pomembne.", DialogueNodes::RazličnikEnota, 1),
+                    gamble("Maso. Ne zdi se mi pomembna.", 50, false, DialogueNodes::RazličnikMasaDa, DialogueNodes::RazličnikMasaNe),      //kot v zapiskih: 50 % možnost, da je bila to prava odločitev
+                ],
+            ));
+            map.insert(DialogueNodes::RazličnikMasaDa, node(
+                "Res je, masa se pri pospešku pokrajša! Zelo lepo.",
+                vec![answer("Seveda, to sem [vedel|vedela].", DialogueNodes::RazličnikEnota, 2)],
+            ));
+            map.insert(DialogueNodes::RazličnikMasaNe, node(
+                "Žal ne, tokrat vas sprašujem po sili trenja. Tam masa šteje.",
+                vec![answer("(zardiš)", DialogueNodes::RazličnikEnota, -1)],
+            ));
+            map.insert(DialogueNodes::RazličnikEnota, node(
+                "Še zadnje: v čem merimo energijo?",
+                vec![
+                    answer("V joulih.", DialogueNodes::RazličnikKonec, 1),
+                    answer("V newtonih.", DialogueNodes::RazličnikKonec, -1),
+                    DialogueResponse {      //only if you were paying attention in the lectures
+                        text: "V joulih, kar je newton krat meter.",
+                        outcome: DialogueOutcome::Continue(DialogueNodes::RazličnikKonec),
+                        condition: Some(Condition::AttentionAtLeast(50)),
+                        score: 2,
+                        ..Default::default()
+                    },
+                ],
+            ));
+            map.insert(DialogueNodes::RazličnikKonec, node(
+                "No, pa sva. Hvala za prijeten pogovor!",
+                vec![DialogueResponse { text: "(čakaš na oceno)", outcome: DialogueOutcome::FinishOralExam, ..Default::default() }],
+            ));
+        }
+
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //the most (or least) points you can get from a node to the end of the exam, every gamble can go either way
+    fn score_range(map: &HashMap<DialogueNodes, DialogueNode>, node: DialogueNodes) -> (i32, i32) {
+        let mut range = (i32::MAX, i32::MIN);
+        for response in &map[&node].responses {
+            let (low, high) = match response.outcome {
+                DialogueOutcome::Continue(next) | DialogueOutcome::StartOralExam(next) => score_range(map, next),
+                DialogueOutcome::Gamble { success, failure, .. } => {
+                    let (success, failure) = (score_range(map, success), score_range(map, failure));
+                    (success.0.min(failure.0), success.1.max(failure.1))
+                }
+                DialogueOutcome::FinishOralExam => (0, 0),
+                _ => continue,
+            };
+            range = (range.0.min(low + response.score), range.1.max(high + response.score));
+        }
+        range
+    }
+
+    #[test]
+    fn gamble_chance_stays_between_5_and_95() {
+        let peteršič = professor(NpcId::Peteršič).unwrap();
+        assert_eq!(gamble_chance(15, false, Some(&peteršič), 0.0, 1.0), 15);        //a pure guess ignores the meters
+        assert_eq!(gamble_chance(55, true, Some(&peteršič), 0.5, 0.0), 55);
+        assert_eq!(gamble_chance(55, true, Some(&peteršič), 0.0, 1.0), 5);
+        assert_eq!(gamble_chance(90, true, Some(&peteršič), 1.0, 0.0), 95);
+        assert!(gamble_chance(50, true, None, 1.0, 0.0) > 50);
+    }
+
+    #[test]
+    fn pass_thresholds() {
+        let peteršič = professor(NpcId::Peteršič).unwrap();
+        assert!(!peteršič.passes(3) && peteršič.passes(4));
+        assert_eq!(peteršič.percent(4), 50);
+        let različnik = professor(NpcId::Različnik).unwrap();
+        assert!(!različnik.passes(2) && različnik.passes(3));
+        assert_eq!(različnik.percent(-2), 0);
+        assert_eq!(različnik.percent(9), 100);
+    }
+
+    #[test]
+    fn exams_can_be_passed_and_failed() {
+        for (npc, start) in [(NpcId::Peteršič, DialogueNodes::PeteršičZačetek), (NpcId::Različnik, DialogueNodes::RazličnikZačetek)] {
+            let mut map = HashMap::new();
+            professor_dialogue(npc, &mut map);
+            let professor = professor(npc).unwrap();
+            let (worst, best) = score_range(&map, start);
+            assert!(professor.passes(best) && !professor.passes(worst), "{:?}", npc);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//grid based A* so anyone walking on their own can get around the walls of a room
+
+use crate::models::gamestate::Wall;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+pub const CELL: f64 = 10.0;     //size of one grid cell in world pixels, smaller = smoother paths but slower search
+
+pub fn overlaps_any(walls: &[Wall], x: f64, y: f64, w: f64, h: f64) -> bool {      //same check as GameState::collides_with_wall, but usable without a GameState
+    walls.iter().any(|wall| {
+        !(x + w <= wall.x
+            || x >= wall.x + wall.width
+            || y + h <= wall.y
+            || y >= wall.y + wall.height)
+    })
+}
+
+struct Grid<'a> {
+    walls: &'a [Wall],
+    cols: i32,
+    rows: i32,
+    w: f64,     //size of whoever is walking, because a cell is only free if the whole body fits there
+    h: f64,
+}
+
+impl Grid<'_> {
+    fn free(&self, (c, r): (i32, i32)) -> bool {
+        if c < 0 || r < 0 || c >= self.cols || r >= self.rows {
+            return false;
+        }
+        let x = c as f64 * CELL;
+        let y = r as f64 * CELL;
+        //the body also can't stick out of the world
+        x + self.w <= self.cols as f64 * CELL
+            && y + self.h <= self.rows as f64 * CELL
+            && !overlaps_any(self.walls, x, y, self.w, self.h)
+    }
+
+    fn cell_of(&self, x: f64, y: f64) -> (i32, i32) {
+        (
+            ((x / CELL).round() as i32).clamp(0, self.cols - 1),
+            ((y / CELL).round() as i32).clamp(0, self.rows - 1),
+        )
+    }
+
+    //a target inside a wall (ex. a table) still gets a path next to it, so we look for the closest free cell in growing rings
+    fn nearest_free(&self, cell: (i32, i32)) -> Option<(i32, i32)> {
+        if self.free(cell) {
+            return Some(cell);
+        }
+        let max_radius = self.cols.max(self.rows);
+        for radius in 1..max_radius {
+            let mut best: Option<((i32, i32), i32)> = None;
+            for dc in -radius..=radius {
+                for dr in -radius..=radius {
+                    if dc.abs() != radius && dr.abs() != radius {
+                        continue;       //only the ring, the inside was already checked
+                    }
+                    let candidate = (cell.0 + dc, cell.1 + dr);
+                    let dist = dc * dc + dr * dr;
+                    if self.free(candidate) && best.is_none_or(|(_, d)| dist < d) {
+                        best = Some((candidate, dist));
+                    }
+                }
+            }
+            if let Some((found, _)) = best {
+                return Some(found);
+            }
+        }
+        None
+    }
+}
+
+#[derive(PartialEq)]
+struct Open {
+    cost: f64,      //distance walked so far + guess of how far it is to the goal
+    cell: (i32, i32),
+}
+
+impl Eq for Open {}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)      //reversed so BinaryHeap gives us the cheapest cell first
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn heuristic(a: (i32, i32), b: (i32, i32)) -> f64 {     //octile distance, exact on an empty 8-directional grid
+    let dx = (a.0 - b.0).abs() as f64;
+    let dy = (a.1 - b.1).abs() as f64;
+    dx.max(dy) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy)
+}
+
+//returns the waypoints (top left corner of the walker) from start to goal, without the start point
+pub fn find_path(
+    walls: &[Wall],
+    world_width: f64,
+    world_height: f64,
+    start: (f64, f64),
+    goal: (f64, f64),
+    w: f64,
+    h: f64,
+) -> Option<Vec<(f64, f64)>> {
+    let grid = Grid {
+        walls,
+        cols: (world_width / CELL) as i32,
+        rows: (world_height / CELL) as i32,
+        w,
+        h,
+    };
+
+    let start_cell = grid.nearest_free(grid.cell_of(start.0, start.1))?;
+    let goal_cell = grid.nearest_free(grid.cell_of(goal.0, goal.1))?;
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+    let mut walked: HashMap<(i32, i32), f64> = HashMap::new();
+    walked.insert(start_cell, 0.0);
+    open.push(Open { cost: heuristic(start_cell, goal_cell), cell: start_cell });
+
+    while let Some(Open { cell, .. }) = open.pop() {
+        if cell == goal_cell {
+            let mut cells = vec![cell];
+            let mut current = cell;
+            while let Some(&previous) = came_from.get(&current) {
+                cells.push(previous);
+                current = previous;
+            }
+            cells.reverse();
+            let mut path = simplify(&cells);
+            //if the goal itself is free we walk exactly there, otherwise we stop at the closest free cell
+            if !overlaps_any(walls, goal.0, goal.1, w, h) && grid.cell_of(goal.0, goal.1) == goal_cell {
+                if let Some(last) = path.last_mut() {
+                    *last = goal;
+                } else {
+                    path.push(goal);
+                }
+            }
+            return Some(path);
+        }
+
+        let current_walked = walked[&cell];
+        for dc in -1..=1 {
+            for dr in -1..=1 {
+                if dc == 0 && dr == 0 {
+                    continue;
+                }
+                let next = (cell.0 + dc, cell.1 + dr);
+                if !grid.free(next) {
+                    continue;
+                }
+                if dc != 0 && dr != 0 && (!grid.free((cell.0 + dc, cell.1)) || !grid.free((cell.0, cell.1 + dr))) {
+                    continue;       //no cutting corners diagonally past a wall
+                }
+                let step = if dc != 0 && dr != 0 { std::f64::consts::SQRT_2 } else { 1.0 };
+                let next_walked = current_walked + step;
+                if walked.get(&next).is_none_or(|&w| next_walked < w) {
+                    walked.insert(next, next_walked);
+                    came_from.insert(next, cell);
+                    open.push(Open { cost: next_walked + heuristic(next, goal_cell), cell: next });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+//we only keep the cells where the direction changes, so the walker goes in straight lines between them
+fn simplify(cells: &[(i32, i32)]) -> Vec<(f64, f64)> {
+    let mut points = Vec::new();
+    for i in 1..cells.len() {
+        let is_last = i + 1 == cells.len();
+        let turns = !is_last && {
+            let before = (cells[i].0 - cells[i - 1].0, cells[i].1 - cells[i - 1].1);
+            let after = (cells[i + 1].0 - cells[i].0, cells[i + 1].1 - cells[i].1);
+            before != after
+        };
+        if is_last || turns {
+            points.push((cells[i].0 as f64 * CELL, cells[i].1 as f64 * CELL));
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(x: f64, y: f64, width: f64, height: f64) -> Wall {
+        Wall { x, y, width, height }
+    }
+
+    #[test]
+    fn empty_room_goes_straight_to_the_goal() {
+        let path = find_path(&[], 400.0, 300.0, (20.0, 20.0), (200.0, 20.0), 10.0, 10.0).unwrap();
+        assert_eq!(path, vec![(200.0, 20.0)]);
+    }
+
+    #[test]
+    fn walks_around_a_wall() {
+        let walls = [wall(100.0, 0.0, 20.0, 250.0)];        //gap only at the bottom
+        let path = find_path(&walls, 400.0, 300.0, (20.0, 20.0), (200.0, 20.0), 10.0, 10.0).unwrap();
+        assert!(path.len() > 1);
+        assert_eq!(path.last(), Some(&(200.0, 20.0)));
+        assert!(path.iter().all(|&(x, y)| !overlaps_any(&walls, x, y, 10.0, 10.0)));
+        assert!(path.iter().any(|&(_, y)| y >= 250.0));        //had to go under it
+    }
+
+    #[test]
+    fn goal_inside_a_wall_stops_next_to_it() {
+        let walls = [wall(180.0, 0.0, 60.0, 60.0)];     //a table
+        let path = find_path(&walls, 400.0, 300.0, (20.0, 20.0), (200.0, 20.0), 10.0, 10.0).unwrap();
+        let &(x, y) = path.last().unwrap();
+        assert!(!overlaps_any(&walls, x, y, 10.0, 10.0));
+    }
+
+    #[test]
+    fn no_path_out_of_a_closed_box() {
+        let walls = [
+            wall(60.0, 60.0, 100.0, 10.0),
+            wall(60.0, 150.0, 100.0, 10.0),
+            wall(60.0, 60.0, 10.0, 100.0),
+            wall(150.0, 60.0, 10.0, 100.0),
+        ];
+        assert_eq!(find_path(&walls, 400.0, 300.0, (100.0, 100.0), (300.0, 250.0), 10.0, 10.0), None);
+    }
+}
@@ -1,4 +1,8 @@
 
+use crate::models::inventory::Inventory;
+use crate::models::character::Character;
+use crate::models::interactable::Interactable;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]   //this is so we can print, copy, compare values of Smer
 pub enum Smer {    //we want to first represent which direction the player is facing/moving in
     Levo,
@@ -8,6 +12,7 @@ pub enum Smer {    //we want to first represent which direction the player is fa
 
 pub struct Player {    //we need to represent the player's state
     // pub name: String,
+    pub character: Character,      //who you're playing as, decides the sprite, starting meters and quirks
     pub x: f64,    //x coordinate on screen, (0,0) is top left
     pub y: f64,
     pub smer: Smer,    //direction of facing
@@ -15,39 +20,62 @@ pub struct Player {    //we need to represent the player's state
     pub height: f64,
     //pub frame: usize,   //picks the current frame
     pub money: i32,
-    pub max_money: i32,
-    // pub attention: u32,
+    pub max_money: i32,     //how much money fills the money bar (you can earn more than that)
+    pub attention: i32,     //pozornost, goes down when you work or study, coffee and sleep bring it back
+    pub max_attention: i32,
     pub anxiety: i32,
     pub max_anxiety: i32,
+    pub inventory: Inventory,       //everything in the backpack
+    pub path: Vec<(f64, f64)>,      //click-to-move waypoints, empty when you walk with the keys
+    pub walk_target: Option<Interactable>,      //clicked on an object or npc, its menu/dialogue opens once you get there
 }
 
 impl Player {       //with this implementation we just want to CREATE a player starting at (x, y) with no movement and frame 0
-    pub fn new(x: f64, y: f64) -> Self {
+    pub fn new(x: f64, y: f64, character: Character) -> Self {
         let image_scale = 1.5;
-        let width = 20.0 * image_scale;       //these are the dimensions of the image multiplied by a random scale (currently 2.9 bc it matches the background player lol) so if you want to change how big he is just scale it differently
-        let height = 62.0 * image_scale;
+        let (image_width, image_height) = character.size();
+        let width = image_width * image_scale;       //these are the dimensions of the image multiplied by a random scale (currently 2.9 bc it matches the background player lol) so if you want to change how big he is just scale it differently
+        let height = image_height * image_scale;
         Player {
+            character,
             x,
             y,
             smer: Smer::Stoji,
             width,
             height,
             //frame: 0,   //when we add different frames for the character movement :)
-            money: 100,
+            money: character.starting_money(),
             max_money: 100,
-            anxiety: 0,
+            attention: character.max_attention(),
+            max_attention: character.max_attention(),
+            anxiety: character.starting_anxiety(),
             max_anxiety: 100,
+            inventory: Inventory::starting(),
+            path: vec![],
+            walk_target: None,
         }
     }
 
     //for smoother movement let's define move_by
 pub fn move_by(&mut self, dx: f64, dy: f64) {
-    self.x = self.x + dx;   //moving the player in x direction
-    self.y = self.y + dy;
+    self.x += dx;   //moving the player in x direction
+    self.y += dy;
 }
 
 pub fn spend_money(&mut self, amount: i32) {
-    self.money = (self.money - amount).max(0); // ne da se zaslužit keša idk
+    self.money = (self.money - amount).max(0);
+}
+
+pub fn earn_money(&mut self, amount: i32) {     //študentsko delo in žepnina, zdaj se da zaslužit keš
+    self.money += amount;
+}
+
+pub fn lose_attention(&mut self, amount: i32) {
+    self.attention = (self.attention - amount).max(0);
+}
+
+pub fn gain_attention(&mut self, amount: i32) {
+    self.attention = (self.attention + amount).min(self.max_attention);
 }
 
 pub fn get_less_anxious(&mut self, amount: i32) {
@@ -0,0 +1,176 @@
+//naloge: small tasks with objectives you have to do in order, the reward goes straight to the player
+
+use crate::models::interactable::NpcId;
+use crate::models::room::Room;
+use crate::models::shop::ShopItem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuestId {
+    JutranjaKava,       //you get it at the start of every run
+    Projektna,      //your friend gives it to you once they promise to start working
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    TalkTo(NpcId),
+    Buy(ShopItem),
+    BeIn {      //get to the room before the time runs out (today, or tomorrow if it's already too late today)
+        room: Room,
+        before: u32,        //minutes after midnight
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestEvent {       //things that happen in the game that objectives listen to
+    Talked(NpcId),
+    Bought(ShopItem),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reward {
+    Money(i32),
+    Attention(i32),
+    Anxiety(i32),       //negative calms you down
+}
+
+impl Objective {
+    pub fn label(&self) -> String {
+        match self {
+            Objective::TalkTo(npc) => format!("Pogovor: {}", npc.name()),
+            Objective::Buy(item) => format!("Kupi: {}", item.name().to_lowercase()),
+            Objective::BeIn { room, before } => format!("Pojdi: {} (do {:02}:{:02})", room.name(), before / 60, before % 60),
+        }
+    }
+
+    //(day, minutes) when the objective fails if it starts now, None if it can't run out
+    pub fn deadline(&self, day: u32, minutes: u32) -> Option<(u32, u32)> {
+        match self {
+            Objective::BeIn { before, .. } if minutes < *before => Some((day, *before)),
+            Objective::BeIn { before, .. } => Some((day + 1, *before)),     //started too late for today, so you get until the same time tomorrow
+            _ => None,
+        }
+    }
+
+    pub fn done_by(&self, event: QuestEvent) -> bool {
+        match (self, event) {
+            (Objective::TalkTo(npc), QuestEvent::Talked(talked)) => *npc == talked,
+            (Objective::Buy(item), QuestEvent::Bought(bought)) => *item == bought,
+            _ => false,
+        }
+    }
+}
+
+impl Reward {
+    pub fn label(&self) -> String {
+        match self {
+            Reward::Money(amount) => format!("+{} €", amount),
+            Reward::Attention(amount) => format!("Pozornost {:+}", amount),
+            Reward::Anxiety(amount) => format!("Anksioznost {:+}", amount),
+        }
+    }
+}
+
+impl QuestId {
+    pub fn title(&self) -> &'static str {
+        match self {
+            QuestId::JutranjaKava => "Jutranja kava",
+            QuestId::Projektna => "Projektna za Programiranje 2",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            QuestId::JutranjaKava => "Brez kave ni faksa. Pa še nekdo te čaka v Mafiji.",
+            QuestId::Projektna => "Projektna se ne bo naredila sama. Preveri v 2. štuku, potem pa povej, kako daleč si.",
+        }
+    }
+
+    pub fn objectives(&self, friend: NpcId) -> Vec<Objective> {        //in the order they have to be done, friend is whoever you're not playing as
+        match self {
+            QuestId::JutranjaKava => vec![Objective::Buy(ShopItem::Kava), Objective::TalkTo(friend)],
+            QuestId::Projektna => vec![
+                Objective::BeIn { room: Room::DrugiŠtuk, before: 18 * 60 },
+                Objective::TalkTo(friend),
+            ],
+        }
+    }
+
+    pub fn rewards(&self) -> Vec<Reward> {
+        match self {
+            QuestId::JutranjaKava => vec![Reward::Anxiety(-5)],
+            QuestId::Projektna => vec![Reward::Attention(15), Reward::Anxiety(-10), Reward::Money(5)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestStatus {
+    Active,
+    Completed,
+    Failed,     //a deadline went by
+}
+
+pub struct QuestProgress {
+    pub id: QuestId,
+    pub objectives: Vec<Objective>,     //filled in when the quest starts (they depend on who you're playing as)
+    pub step: usize,        //index of the current objective
+    pub deadline: Option<(u32, u32)>,       //(day, minutes) when the current objective fails, see Objective::deadline
+    pub status: QuestStatus,
+}
+
+impl QuestProgress {
+    pub fn objective(&self) -> Option<Objective> {
+        self.objectives.get(self.step).copied()
+    }
+}
+
+pub struct QuestLog {
+    pub quests: Vec<QuestProgress>,     //in the order you got them
+}
+
+impl QuestLog {
+    pub fn new() -> Self {
+        QuestLog { quests: vec![] }
+    }
+
+    //every quest can only be taken once per run, returns whether it's new
+    pub fn start(&mut self, id: QuestId, day: u32, minutes: u32, friend: NpcId) -> bool {
+        if self.quests.iter().any(|quest| quest.id == id) {
+            return false;
+        }
+        let objectives = id.objectives(friend);
+        let deadline = objectives.first().and_then(|objective| objective.deadline(day, minutes));
+        self.quests.push(QuestProgress { id, objectives, step: 0, deadline, status: QuestStatus::Active });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadline_is_today_or_tomorrow() {
+        let objective = Objective::BeIn { room: Room::DrugiŠtuk, before: 18 * 60 };
+        assert_eq!(objective.deadline(3, 10 * 60), Some((3, 18 * 60)));
+        assert_eq!(objective.deadline(3, 23 * 60 + 55), Some((4, 18 * 60)));       //started just before midnight
+        assert_eq!(Objective::TalkTo(NpcId::Ema).deadline(3, 10 * 60), None);
+    }
+
+    #[test]
+    fn objectives_listen_to_their_own_events() {
+        assert!(Objective::Buy(ShopItem::Kava).done_by(QuestEvent::Bought(ShopItem::Kava)));
+        assert!(!Objective::Buy(ShopItem::Kava).done_by(QuestEvent::Talked(NpcId::Ema)));
+        assert!(!Objective::TalkTo(NpcId::Ema).done_by(QuestEvent::Talked(NpcId::Lan)));
+    }
+
+    #[test]
+    fn a_quest_starts_only_once() {
+        let mut log = QuestLog::new();
+        assert!(log.start(QuestId::Projektna, 1, 20 * 60, NpcId::Ema));
+        assert!(!log.start(QuestId::Projektna, 2, 8 * 60, NpcId::Ema));
+        assert_eq!(log.quests.len(), 1);
+        assert_eq!(log.quests[0].deadline, Some((2, 18 * 60)));
+        assert_eq!(log.quests[0].objective(), Some(Objective::BeIn { room: Room::DrugiŠtuk, before: 18 * 60 }));
+    }
+}
@@ -0,0 +1,43 @@
+//tiny random number generator (xorshift), so we don't need a whole crate for dice rolls
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed.max(1) }     //xorshift gets stuck on 0
+    }
+
+    pub fn from_time() -> Self {        //different every time you open the game
+        Rng::new(js_sys::Date::now() as u64 ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {     //random number in 0..n
+        if n == 0 {
+            return 0;
+        }
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {       //true with the given probability (0.0 - 1.0)
+        let roll = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;      //uniform in 0..1
+        roll < probability
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//every room has its own background, walls and interactive items so we keep them together here
+
+use crate::models::gamestate::{Item, Wall};
+use crate::models::interactable::{Interactable, Objects};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Room {
+    Mafija,
+    DrugiŠtuk,      //2. štuk, where the lectures (and later written exams) are
+    Kabinet,        //kabinet profesorjev, ustni izpiti
+}
+
+pub const ALL_ROOMS: [Room; 3] = [Room::Mafija, Room::DrugiŠtuk, Room::Kabinet];
+
+impl Room {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Room::Mafija => "Mafija",
+            Room::DrugiŠtuk => "2. štuk",
+            Room::Kabinet => "Kabinet",
+        }
+    }
+
+    pub fn background(&self) -> &'static str {
+        match self {
+            Room::Mafija => "/static/background/mafija_1.png",
+            Room::DrugiŠtuk => "/static/background/start/classroom_pixelart.png",
+            Room::Kabinet => "/static/background/Kavarna.png",     //placeholder until we draw a proper kabinet
+        }
+    }
+
+    //where you (or an npc) appear when you come through the door
+    pub fn entrance(&self) -> (f64, f64) {
+        match self {
+            Room::Mafija => (1060., 490.),
+            Room::DrugiŠtuk => (60., 480.),
+            Room::Kabinet => (280., 470.),
+        }
+    }
+
+    pub fn walls(&self) -> Vec<Wall> {
+        match self {
+            Room::Mafija => vec![
+                Wall { x: 60., y: 0., width: 390., height: 60. }, // pult
+                Wall { x: 1003., y: 595., width: 165., height: 12. }, // vrata
+                Wall { x: 50., y: 380., width: 80., height: 130. }, // miza spodaj prva
+                Wall { x: 260., y: 420., width: 70., height: 100. }, // miza spodaj druga
+                Wall { x: 440., y: 415., width: 90., height: 100. }, // miza spodaj tretja
+                Wall { x: 700., y: 370., width: 70., height: 200. }, // miza spodaj četrta
+                Wall { x: 200., y: 200., width: 100., height: 90. }, // miza zgoraj leva
+                Wall { x: 490., y: 190., width: 180., height: 90. }, // miza zgoraj desna
+                Wall { x: 800., y: 0., width: 400., height: 70. }, // pult 2
+            ],
+            Room::DrugiŠtuk => vec![
+                Wall { x: 0., y: 0., width: 1200., height: 110. }, // tabla
+                Wall { x: 180., y: 300., width: 260., height: 60. }, // klop leva
+                Wall { x: 480., y: 300., width: 240., height: 60. }, // klop sredinska
+                Wall { x: 780., y: 300., width: 260., height: 60. }, // klop desna
+                Wall { x: 300., y: 540., width: 600., height: 60. }, // katedra
+                Wall { x: 20., y: 595., width: 150., height: 12. }, // vrata
+            ],
+            Room::Kabinet => vec![
+                Wall { x: 0., y: 0., width: 1200., height: 20. }, // zgornja stena
+                Wall { x: 0., y: 0., width: 20., height: 600. }, // leva stena
+                Wall { x: 1180., y: 0., width: 20., height: 600. }, // desna stena
+                Wall { x: 0., y: 580., width: 197., height: 20. }, // spodnja stena levo od vrat
+                Wall { x: 412., y: 580., width: 788., height: 20. }, // spodnja stena desno od vrat
+                Wall { x: 500., y: 200., width: 200., height: 80. }, // miza
+                Wall { x: 197., y: 590., width: 215., height: 10. }, // vrata
+            ],
+        }
+    }
+
+    pub fn items(&self) -> Vec<Item> {      //npcs aren't here because they walk around, see GameState::items_in_room
+        match self {
+            Room::Mafija => vec![
+                Item { kind: Interactable::Object(Objects::Counter), x: 60., y: 10., width: 390., height: 65. },    //counter
+                Item { kind: Interactable::Object(Objects::Door), x: 1003., y: 595., width: 165., height: 12. },    //bottom door
+            ],
+            Room::DrugiŠtuk => vec![
+                Item { kind: Interactable::Object(Objects::Door), x: 20., y: 595., width: 150., height: 12. },
+                Item { kind: Interactable::Object(Objects::ExamDesk), x: 300., y: 540., width: 600., height: 60. },     //katedra
+            ],
+            Room::Kabinet => vec![
+                Item { kind: Interactable::Object(Objects::Door), x: 197., y: 590., width: 215., height: 10. },
+            ],
+        }
+    }
+}
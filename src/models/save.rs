@@ -0,0 +1,340 @@
+//shranjena igra: one slot in localStorage, one line per thing ("meters 80 65 20", "item Knjiga 1"...)
+//where the npcs are standing isn't saved, they just walk back to their schedule
+
+use crate::models::character::Character;
+use crate::models::economy::{Transaction, TransactionKind};
+use crate::models::gamestate::GameState;
+use crate::models::interactable::NpcId;
+use crate::models::inventory::ItemKind;
+use crate::models::mishap::MishapRecord;
+use crate::models::quest::{QuestId, QuestProgress, QuestStatus};
+use crate::models::room::Room;
+use crate::models::exam::Subject;
+use crate::models::shop::{Payment, Purchase, ShopItem};
+use crate::models::storage;
+use crate::models::transcript::ExamAttempt;
+
+const STORAGE_KEY: &str = "fakslife_save";
+
+//everything that goes into the save file by name, don't change the ids or old saves stop loading
+trait Saved: Copy + PartialEq + 'static {
+    const ALL: &'static [Self];
+    fn id(&self) -> &'static str;
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|value| value.id() == id)
+    }
+}
+
+impl Saved for Character {
+    const ALL: &'static [Self] = &[Character::Lan, Character::Ema];
+    fn id(&self) -> &'static str {
+        match self {
+            Character::Lan => "lan",
+            Character::Ema => "ema",
+        }
+    }
+}
+
+impl Saved for Room {
+    const ALL: &'static [Self] = &[Room::Mafija, Room::DrugiŠtuk, Room::Kabinet];
+    fn id(&self) -> &'static str {
+        match self {
+            Room::Mafija => "mafija",
+            Room::DrugiŠtuk => "drugi_stuk",
+            Room::Kabinet => "kabinet",
+        }
+    }
+}
+
+impl Saved for ItemKind {
+    const ALL: &'static [Self] = &[ItemKind::BonZaTortiljo, ItemKind::Knjiga, ItemKind::Telefon, ItemKind::ListSFormulami];
+    fn id(&self) -> &'static str {
+        match self {
+            ItemKind::BonZaTortiljo => "bon",
+            ItemKind::Knjiga => "knjiga",
+            ItemKind::Telefon => "telefon",
+            ItemKind::ListSFormulami => "list_s_formulami",
+        }
+    }
+}
+
+impl Saved for Subject {
+    const ALL: &'static [Self] = &[Subject::Analiza1, Subject::Fizika, Subject::Programiranje2, Subject::Stop, Subject::PodatkovneBaze];
+    fn id(&self) -> &'static str {
+        match self {
+            Subject::Analiza1 => "analiza1",
+            Subject::Fizika => "fizika",
+            Subject::Programiranje2 => "programiranje2",
+            Subject::Stop => "stop",
+            Subject::PodatkovneBaze => "podatkovne_baze",
+        }
+    }
+}
+
+impl Saved for NpcId {
+    const ALL: &'static [Self] = &[NpcId::Ema, NpcId::Lan, NpcId::Peteršič, NpcId::Različnik];
+    fn id(&self) -> &'static str {
+        match self {
+            NpcId::Ema => "ema",
+            NpcId::Lan => "lan",
+            NpcId::Peteršič => "petersic",
+            NpcId::Različnik => "razlicnik",
+        }
+    }
+}
+
+impl Saved for QuestId {
+    const ALL: &'static [Self] = &[QuestId::JutranjaKava, QuestId::Projektna];
+    fn id(&self) -> &'static str {
+        match self {
+            QuestId::JutranjaKava => "jutranja_kava",
+            QuestId::Projektna => "projektna",
+        }
+    }
+}
+
+impl Saved for QuestStatus {
+    const ALL: &'static [Self] = &[QuestStatus::Active, QuestStatus::Completed, QuestStatus::Failed];
+    fn id(&self) -> &'static str {
+        match self {
+            QuestStatus::Active => "active",
+            QuestStatus::Completed => "completed",
+            QuestStatus::Failed => "failed",
+        }
+    }
+}
+
+impl Saved for ShopItem {
+    const ALL: &'static [Self] = &[ShopItem::Kava, ShopItem::Tortilja, ShopItem::Rogljiček];
+    fn id(&self) -> &'static str {
+        match self {
+            ShopItem::Kava => "kava",
+            ShopItem::Tortilja => "tortilja",
+            ShopItem::Rogljiček => "rogljicek",
+        }
+    }
+}
+
+fn payment_id(payment: Payment) -> String {
+    match payment {
+        Payment::Money(price) => format!("money {}", price),
+        Payment::Voucher => "voucher".to_string(),
+        Payment::Treat(npc) => format!("treat {}", npc.id()),
+    }
+}
+
+fn transaction_kind_id(kind: TransactionKind) -> String {
+    match kind {
+        TransactionKind::Purchase(item) => format!("purchase {}", item.id()),
+        TransactionKind::Allowance => "allowance".to_string(),
+        TransactionKind::Shift => "shift".to_string(),
+        TransactionKind::BookSold => "book_sold".to_string(),
+        TransactionKind::Mishap => "mishap".to_string(),
+        TransactionKind::QuestReward => "quest_reward".to_string(),
+    }
+}
+
+fn parse_transaction_kind(parts: &[&str]) -> Option<TransactionKind> {
+    match parts {
+        ["purchase", item] => Some(TransactionKind::Purchase(ShopItem::from_id(item)?)),
+        ["allowance"] => Some(TransactionKind::Allowance),
+        ["shift"] => Some(TransactionKind::Shift),
+        ["book_sold"] => Some(TransactionKind::BookSold),
+        ["mishap"] => Some(TransactionKind::Mishap),
+        ["quest_reward"] => Some(TransactionKind::QuestReward),
+        _ => None,
+    }
+}
+
+fn parse_payment(parts: &[&str]) -> Option<Payment> {
+    match parts {
+        ["money", price] => Some(Payment::Money(price.parse().ok()?)),
+        ["voucher"] => Some(Payment::Voucher),
+        ["treat", npc] => Some(Payment::Treat(NpcId::from_id(npc)?)),
+        _ => None,
+    }
+}
+
+pub fn exists() -> bool {
+    storage::load(STORAGE_KEY).is_some()
+}
+
+pub fn save(game_state: &GameState) {
+    let player = &game_state.player;
+    let mut lines = vec![
+        format!("character {}", player.character.id()),
+        format!("room {} {} {}", game_state.current_room.id(), player.x, player.y),
+        format!("meters {} {} {}", player.money, player.attention, player.anxiety),
+        format!("clock {} {} {}", game_state.clock.day, game_state.clock.minutes, game_state.day_started),
+        format!("year {}", game_state.transcript.year),
+    ];
+    for slot in &player.inventory.slots {
+        lines.push(format!("item {} {}", slot.kind.id(), slot.count));
+    }
+    for attempt in &game_state.transcript.attempts {
+        lines.push(format!("exam {} {} {} {}", attempt.day, attempt.subject.id(), attempt.oral, attempt.percent));
+    }
+    for npc in NpcId::ALL {
+        let last_favor = game_state.affinity.last_favor(*npc).map_or("-".to_string(), |day| day.to_string());
+        lines.push(format!("affinity {} {} {}", npc.id(), game_state.affinity.get(*npc), last_favor));
+    }
+    for quest in &game_state.quests.quests {
+        let deadline = quest.deadline.map_or("-".to_string(), |(day, minutes)| format!("{} {}", day, minutes));
+        lines.push(format!("quest {} {} {} {}", quest.id.id(), quest.step, quest.status.id(), deadline));
+    }
+    for record in &game_state.mishap_log {
+        lines.push(format!("mishap {} {} {}", record.day, record.minutes, record.index));
+    }
+    for purchase in &game_state.purchases {
+        lines.push(format!("purchase {} {} {} {}", purchase.day, purchase.minutes, purchase.item.id(), payment_id(purchase.payment)));
+    }
+    for transaction in &game_state.transactions {
+        lines.push(format!("transaction {} {} {} {}", transaction.day, transaction.minutes, transaction.amount, transaction_kind_id(transaction.kind)));
+    }
+    storage::save(STORAGE_KEY, &lines.join("\n"));
+}
+
+//starts a fresh run with the saved character and fills it in line by line, lines that don't make sense are skipped
+pub fn load(game_state: &mut GameState) -> bool {
+    let Some(saved) = storage::load(STORAGE_KEY) else {
+        return false;
+    };
+    let lines: Vec<Vec<&str>> = saved.lines().map(|line| line.split_whitespace().collect()).collect();
+    let Some(character) = lines.iter().find_map(|line| match line.as_slice() {
+        ["character", id] => Character::from_id(id),
+        _ => None,
+    }) else {
+        return false;
+    };
+
+    game_state.reset_run(character);
+    game_state.player.inventory.slots.clear();
+    game_state.quests.quests.clear();
+    game_state.transactions.clear();
+    game_state.notification = None;     //no "new quest" popup for a quest you already had
+    let friend = character.friend();
+
+    for line in &lines {
+        match line.as_slice() {
+            ["room", room, x, y] => {
+                if let (Some(room), Ok(x), Ok(y)) = (Room::from_id(room), x.parse(), y.parse()) {
+                    game_state.enter_room(room);
+                    game_state.player.x = x;
+                    game_state.player.y = y;
+                }
+            }
+            ["meters", money, attention, anxiety] => {
+                if let (Ok(money), Ok(attention), Ok(anxiety)) = (money.parse(), attention.parse(), anxiety.parse()) {
+                    game_state.player.money = money;
+                    game_state.player.attention = attention;
+                    game_state.player.anxiety = anxiety;
+                }
+            }
+            ["clock", day, minutes, day_started] => {
+                if let (Ok(day), Ok(minutes), Ok(day_started)) = (day.parse(), minutes.parse(), day_started.parse()) {
+                    game_state.clock.day = day;
+                    game_state.clock.minutes = minutes;
+                    game_state.day_started = day_started;
+                }
+            }
+            ["year", year] => {
+                if let Ok(year) = year.parse() {
+                    game_state.transcript.year = year;
+                }
+            }
+            ["item", kind, count] => {
+                if let (Some(kind), Ok(count)) = (ItemKind::from_id(kind), count.parse()) {
+                    game_state.player.inventory.add(kind, count);
+                }
+            }
+            ["exam", day, subject, oral, percent] => {
+                if let (Ok(day), Some(subject), Ok(oral), Ok(percent)) = (day.parse(), Subject::from_id(subject), oral.parse(), percent.parse()) {
+                    game_state.transcript.attempts.push(ExamAttempt::new(day, subject, oral, percent));
+                }
+            }
+            ["affinity", npc, value, last_favor] => {
+                if let (Some(npc), Ok(value)) = (NpcId::from_id(npc), value.parse()) {
+                    game_state.affinity.restore(npc, value, last_favor.parse().ok());
+                }
+            }
+            ["quest", id, step, status, deadline @ ..] => {
+                let deadline = match deadline {
+                    [day, minutes] => day.parse().ok().zip(minutes.parse().ok()),
+                    _ => None,      //"-", nothing can run out
+                };
+                if let (Some(id), Ok(step), Some(status)) = (QuestId::from_id(id), step.parse(), QuestStatus::from_id(status)) {
+                    game_state.quests.quests.push(QuestProgress { id, objectives: id.objectives(friend), step, deadline, status });
+                }
+            }
+            ["mishap", day, minutes, index] => {
+                if let (Ok(day), Ok(minutes), Ok(index)) = (day.parse(), minutes.parse(), index.parse::<usize>()) {
+                    if index < game_state.mishaps.len() {       //the mishap files might have changed since
+                        game_state.mishap_log.push(MishapRecord { day, minutes, index });
+                    }
+                }
+            }
+            ["purchase", day, minutes, item, payment @ ..] => {
+                if let (Ok(day), Ok(minutes), Some(item), Some(payment)) = (day.parse(), minutes.parse(), ShopItem::from_id(item), parse_payment(payment)) {
+                    game_state.purchases.push(Purchase { day, minutes, item, payment });
+                }
+            }
+            ["transaction", day, minutes, amount, kind @ ..] => {
+                if let (Ok(day), Ok(minutes), Ok(amount), Some(kind)) = (day.parse(), minutes.parse(), amount.parse(), parse_transaction_kind(kind)) {
+                    game_state.transactions.push(Transaction { day, minutes, amount, kind });
+                }
+            }
+            _ => {}
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fmt::Debug;
+
+    fn ids_round_trip<T: Saved + Debug>() {
+        let ids: HashSet<&str> = T::ALL.iter().map(|value| value.id()).collect();
+        assert_eq!(ids.len(), T::ALL.len(), "two values share an id");
+        for value in T::ALL {
+            assert_eq!(T::from_id(value.id()), Some(*value));
+            assert!(!value.id().contains(char::is_whitespace), "{:?}", value);     //lines are split on spaces
+        }
+    }
+
+    #[test]
+    fn every_saved_id_loads_back() {
+        ids_round_trip::<Character>();
+        ids_round_trip::<Room>();
+        ids_round_trip::<ItemKind>();
+        ids_round_trip::<Subject>();
+        ids_round_trip::<NpcId>();
+        ids_round_trip::<QuestId>();
+        ids_round_trip::<QuestStatus>();
+        ids_round_trip::<ShopItem>();
+    }
+
+    #[test]
+    fn payments_and_transactions_load_back() {
+        for payment in [Payment::Money(3), Payment::Voucher, Payment::Treat(NpcId::Ema)] {
+            let id = payment_id(payment);
+            assert_eq!(parse_payment(&id.split_whitespace().collect::<Vec<_>>()), Some(payment));
+        }
+        let kinds = [
+            TransactionKind::Purchase(ShopItem::Rogljiček),
+            TransactionKind::Allowance,
+            TransactionKind::Shift,
+            TransactionKind::BookSold,
+            TransactionKind::Mishap,
+            TransactionKind::QuestReward,
+        ];
+        for kind in kinds {
+            let id = transaction_kind_id(kind);
+            assert_eq!(parse_transaction_kind(&id.split_whitespace().collect::<Vec<_>>()), Some(kind));
+        }
+        assert_eq!(parse_payment(&["money", "veliko"]), None);
+    }
+}
@@ -0,0 +1,280 @@
+//nastavitve: volume, text speed, display and accessibility options, saved in localStorage and applied right away
+
+use crate::models::storage;
+use web_sys::window;
+
+const STORAGE_KEY: &str = "fakslife_settings";
+const VOLUME_STEP: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSpeed {
+    Slow,
+    Normal,
+    Fast,
+    Instant,
+}
+
+const TEXT_SPEEDS: [TextSpeed; 4] = [TextSpeed::Slow, TextSpeed::Normal, TextSpeed::Fast, TextSpeed::Instant];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Slovenščina,
+    English,       //only menus for now, the rest of the game is still in slovenian
+}
+
+const LANGUAGES: [Language; 2] = [Language::Slovenščina, Language::English];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsRow {     //one line on the settings screen
+    MusicVolume,
+    SfxVolume,
+    TextSpeed,
+    Fullscreen,
+    PixelPerfect,
+    ReducedMotion,
+    Language,
+    KeyBindings,        //opens the key bindings screen
+    Back,
+}
+
+pub const SETTINGS_ROWS: [SettingsRow; 9] = [
+    SettingsRow::MusicVolume,
+    SettingsRow::SfxVolume,
+    SettingsRow::TextSpeed,
+    SettingsRow::Fullscreen,
+    SettingsRow::PixelPerfect,
+    SettingsRow::ReducedMotion,
+    SettingsRow::Language,
+    SettingsRow::KeyBindings,
+    SettingsRow::Back,
+];
+
+impl TextSpeed {
+    fn id(&self) -> &'static str {      //what gets saved in localStorage, don't change these
+        match self {
+            TextSpeed::Slow => "slow",
+            TextSpeed::Normal => "normal",
+            TextSpeed::Fast => "fast",
+            TextSpeed::Instant => "instant",
+        }
+    }
+
+    //ticks per letter for someone who talks at `base` speed, None = the whole line at once
+    pub fn ticks_per_letter(&self, base: u32) -> Option<u32> {
+        match self {
+            TextSpeed::Slow => Some(base * 2),
+            TextSpeed::Normal => Some(base),
+            TextSpeed::Fast => Some(base.div_ceil(2)),
+            TextSpeed::Instant => None,
+        }
+    }
+}
+
+impl Language {
+    fn id(&self) -> &'static str {
+        match self {
+            Language::Slovenščina => "sl",
+            Language::English => "en",
+        }
+    }
+
+    pub fn pick(&self, sl: &'static str, en: &'static str) -> &'static str {       //the same label in both languages, the right one comes out
+        match self {
+            Language::Slovenščina => sl,
+            Language::English => en,
+        }
+    }
+}
+
+fn next<T: Copy + PartialEq>(all: &[T], current: T, step: i32) -> T {      //left/right on a setting with a few choices, wraps around
+    let index = all.iter().position(|value| *value == current).unwrap_or(0) as i32;
+    all[(index + step).rem_euclid(all.len() as i32) as usize]
+}
+
+pub struct Settings {
+    pub music_volume: u32,      //0-100
+    pub sfx_volume: u32,        //dialogue blips
+    pub text_speed: TextSpeed,
+    pub fullscreen: bool,
+    pub pixel_perfect: bool,        //only whole number scales, so pixel art stays sharp (there might be black borders)
+    pub reduced_motion: bool,       //no typewriter text, no waiting on transitions
+    pub language: Language,
+}
+
+impl Settings {
+    pub fn default_settings() -> Self {
+        Settings {
+            music_volume: 70,
+            sfx_volume: 70,
+            text_speed: TextSpeed::Normal,
+            fullscreen: false,
+            pixel_perfect: false,
+            reduced_motion: false,
+            language: Language::Slovenščina,
+        }
+    }
+
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY).map_or_else(Settings::default_settings, |saved| Settings::from_text(&saved))
+    }
+
+    //saved as one line per setting: "music_volume 70", anything missing or broken stays at its default
+    fn from_text(saved: &str) -> Self {
+        let mut settings = Settings::default_settings();
+        for line in saved.lines() {
+            let Some((key, value)) = line.split_once(' ') else {
+                continue;
+            };
+            match key {
+                "music_volume" => settings.music_volume = value.parse::<u32>().map_or(settings.music_volume, |v| v.min(100)),
+                "sfx_volume" => settings.sfx_volume = value.parse::<u32>().map_or(settings.sfx_volume, |v| v.min(100)),
+                "text_speed" => settings.text_speed = TEXT_SPEEDS.iter().copied().find(|speed| speed.id() == value).unwrap_or(settings.text_speed),
+                "fullscreen" => settings.fullscreen = value == "true",
+                "pixel_perfect" => settings.pixel_perfect = value == "true",
+                "reduced_motion" => settings.reduced_motion = value == "true",
+                "language" => settings.language = LANGUAGES.iter().copied().find(|language| language.id() == value).unwrap_or(settings.language),
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    fn to_text(&self) -> String {
+        let lines = [
+            format!("music_volume {}", self.music_volume),
+            format!("sfx_volume {}", self.sfx_volume),
+            format!("text_speed {}", self.text_speed.id()),
+            format!("fullscreen {}", self.fullscreen),
+            format!("pixel_perfect {}", self.pixel_perfect),
+            format!("reduced_motion {}", self.reduced_motion),
+            format!("language {}", self.language.id()),
+        ];
+        lines.join("\n")
+    }
+
+    fn save(&self) {
+        storage::save(STORAGE_KEY, &self.to_text());
+    }
+
+    //left (-1) or right (+1) on a row, toggles flip either way, saves and applies what it can right away
+    pub fn change(&mut self, row: SettingsRow, step: i32) {
+        let volume = |value: u32| (value as i32 + step * VOLUME_STEP as i32).clamp(0, 100) as u32;
+        match row {
+            SettingsRow::MusicVolume => self.music_volume = volume(self.music_volume),     //Audio::tick picks it up
+            SettingsRow::SfxVolume => self.sfx_volume = volume(self.sfx_volume),
+            SettingsRow::TextSpeed => self.text_speed = next(&TEXT_SPEEDS, self.text_speed, step),
+            SettingsRow::Fullscreen => {
+                self.fullscreen = !self.fullscreen;
+                self.apply_fullscreen();
+            }
+            SettingsRow::PixelPerfect => self.pixel_perfect = !self.pixel_perfect,     //update_viewport picks it up on the next tick
+            SettingsRow::ReducedMotion => self.reduced_motion = !self.reduced_motion,
+            SettingsRow::Language => self.language = next(&LANGUAGES, self.language, step),
+            SettingsRow::KeyBindings | SettingsRow::Back => return,
+        }
+        self.save();
+    }
+
+    pub fn label(&self, row: SettingsRow) -> &'static str {
+        let language = self.language;
+        match row {
+            SettingsRow::MusicVolume => language.pick("Glasba", "Music"),
+            SettingsRow::SfxVolume => language.pick("Zvočni učinki", "Sound effects"),
+            SettingsRow::TextSpeed => language.pick("Hitrost besedila", "Text speed"),
+            SettingsRow::Fullscreen => language.pick("Celozaslonski način", "Fullscreen"),
+            SettingsRow::PixelPerfect => language.pick("Ostre slikovne pike", "Pixel-perfect scaling"),
+            SettingsRow::ReducedMotion => language.pick("Manj animacij", "Reduced motion"),
+            SettingsRow::Language => language.pick("Jezik", "Language"),
+            SettingsRow::KeyBindings => language.pick("Tipke", "Key bindings"),
+            SettingsRow::Back => language.pick("Nazaj", "Back"),
+        }
+    }
+
+    pub fn value(&self, row: SettingsRow) -> String {       //shown on the right of the row, empty for buttons
+        let language = self.language;
+        let on_off = |on: bool| if on { language.pick("vklopljeno", "on") } else { language.pick("izklopljeno", "off") }.to_string();
+        match row {
+            SettingsRow::MusicVolume => format!("{} %", self.music_volume),
+            SettingsRow::SfxVolume => format!("{} %", self.sfx_volume),
+            SettingsRow::TextSpeed => match self.text_speed {
+                TextSpeed::Slow => language.pick("počasi", "slow"),
+                TextSpeed::Normal => language.pick("normalno", "normal"),
+                TextSpeed::Fast => language.pick("hitro", "fast"),
+                TextSpeed::Instant => language.pick("takoj", "instant"),
+            }
+            .to_string(),
+            SettingsRow::Fullscreen => on_off(self.fullscreen),
+            SettingsRow::PixelPerfect => on_off(self.pixel_perfect),
+            SettingsRow::ReducedMotion => on_off(self.reduced_motion),
+            SettingsRow::Language => match self.language {
+                Language::Slovenščina => "slovenščina".to_string(),
+                Language::English => "English".to_string(),
+            },
+            SettingsRow::KeyBindings | SettingsRow::Back => String::new(),
+        }
+    }
+
+    pub fn ticks_per_letter(&self, base: u32) -> Option<u32> {     //typewriter speed after the text speed and reduced motion settings
+        if self.reduced_motion {
+            return None;
+        }
+        self.text_speed.ticks_per_letter(base)
+    }
+
+    pub fn sfx_gain(&self) -> f32 {     //0-1
+        self.sfx_volume as f32 / 100.0
+    }
+
+    //browsers only allow going fullscreen right after a click or key press, so this is called from those (and when you press Start)
+    pub fn apply_fullscreen(&self) {
+        let Some(document) = window().and_then(|window| window.document()) else {
+            return;
+        };
+        let is_fullscreen = document.fullscreen_element().is_some();
+        if self.fullscreen && !is_fullscreen {
+            if let Some(root) = document.document_element() {
+                let _ = root.request_fullscreen();
+            }
+        } else if !self.fullscreen && is_fullscreen {
+            document.exit_fullscreen();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_settings_load_back() {
+        let mut settings = Settings::default_settings();
+        settings.music_volume = 30;
+        settings.text_speed = TextSpeed::Fast;
+        settings.pixel_perfect = true;
+        settings.language = Language::English;
+        let loaded = Settings::from_text(&settings.to_text());
+        assert_eq!(loaded.music_volume, 30);
+        assert_eq!(loaded.sfx_volume, 70);
+        assert_eq!(loaded.text_speed, TextSpeed::Fast);
+        assert!(loaded.pixel_perfect && !loaded.fullscreen && !loaded.reduced_motion);
+        assert_eq!(loaded.language, Language::English);
+    }
+
+    #[test]
+    fn broken_lines_keep_the_default() {
+        let loaded = Settings::from_text("music_volume 250\nsfx_volume glasno\ntext_speed warp\nnonsense\nfullscreen true");
+        assert_eq!(loaded.music_volume, 100);       //clamped
+        assert_eq!(loaded.sfx_volume, 70);
+        assert_eq!(loaded.text_speed, TextSpeed::Normal);
+        assert!(loaded.fullscreen);
+    }
+
+    #[test]
+    fn reduced_motion_means_instant_text() {
+        let mut settings = Settings::default_settings();
+        settings.text_speed = TextSpeed::Slow;
+        assert_eq!(settings.ticks_per_letter(2), Some(4));
+        settings.reduced_motion = true;
+        assert_eq!(settings.ticks_per_letter(2), None);
+    }
+}
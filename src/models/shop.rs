@@ -0,0 +1,65 @@
+//the counter in Mafija: what you can buy there, for how much, and how you paid
+
+use crate::models::interactable::NpcId;
+
+pub const DAILY_VOUCHERS: u32 = 2;      //how many študentski boni you get every morning (unused ones don't carry over)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShopItem {
+    Kava,
+    Tortilja,
+    Rogljiček,
+}
+
+pub const COUNTER_ITEMS: [ShopItem; 3] = [ShopItem::Kava, ShopItem::Tortilja, ShopItem::Rogljiček];     //price list in the order it's shown
+
+impl ShopItem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShopItem::Kava => "Kava",
+            ShopItem::Tortilja => "Tortilja",
+            ShopItem::Rogljiček => "Rogljiček",
+        }
+    }
+
+    pub fn price(&self) -> i32 {        //in money
+        match self {
+            ShopItem::Kava => 2,
+            ShopItem::Tortilja => 5,
+            ShopItem::Rogljiček => 3,
+        }
+    }
+
+    pub fn accepts_voucher(&self) -> bool {     //only proper meals can be paid with a bon
+        matches!(self, ShopItem::Tortilja)
+    }
+
+    pub fn anxiety_change(&self) -> i32 {       //what it does to you once you eat/drink it
+        match self {
+            ShopItem::Kava => 5,
+            ShopItem::Tortilja => -8,
+            ShopItem::Rogljiček => -4,
+        }
+    }
+
+    pub fn attention_change(&self) -> i32 {     //kava ti izboljša pozornost
+        match self {
+            ShopItem::Kava => 20,
+            ShopItem::Tortilja | ShopItem::Rogljiček => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payment {
+    Money(i32),
+    Voucher,
+    Treat(NpcId),       //somebody else paid (a favor)
+}
+
+pub struct Purchase {       //one line in the purchase history
+    pub day: u32,
+    pub minutes: u32,
+    pub item: ShopItem,
+    pub payment: Payment,
+}
@@ -0,0 +1,17 @@
+//localStorage in the browser: small things that have to survive between runs (and page reloads)
+
+use web_sys::{window, Storage};
+
+fn local_storage() -> Option<Storage> {     //None if the browser doesn't let us (private mode etc.), then nothing is remembered
+    window()?.local_storage().ok()?
+}
+
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+pub fn save(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(key, value);       //if it doesn't work, it just won't be remembered
+    }
+}
@@ -0,0 +1,71 @@
+//dotik: on phones and tablets there's an on-screen joystick (bottom left) and action buttons (bottom right), they feed the same actions as the keyboard
+
+use crate::models::gamepad::{deadzone, STICK_PRESS};
+use crate::models::input::{Action, ALL_ACTIONS};
+use std::collections::HashSet;
+use web_sys::window;
+
+pub const JOYSTICK_RADIUS: f64 = 60.0;      //px, the knob can go this far from the center
+pub const JOYSTICK_MARGIN: f64 = 30.0;      //px from the left and bottom edge of the screen
+
+//the action buttons, bottom right, in the order they're drawn
+pub const TOUCH_BUTTONS: [(Action, &str); 4] = [
+    (Action::Interact, "F"),
+    (Action::Confirm, "OK"),
+    (Action::Cancel, "X"),
+    (Action::Inventory, "I"),
+];
+
+pub struct TouchInput {
+    pub enabled: bool,      //only touch devices get the on-screen controls
+    pub stick: (f64, f64),      //same as the controller stick: after the deadzone, from -1 to 1
+    held: HashSet<Action>,      //directions the joystick is pushed far enough in
+}
+
+impl TouchInput {
+    pub fn new() -> Self {
+        let enabled = window().is_some_and(|window| window.navigator().max_touch_points() > 0);
+        TouchInput { enabled, ..TouchInput::off() }
+    }
+
+    pub fn off() -> Self {      //no on-screen controls until a touch comes in
+        TouchInput { enabled: false, stick: (0.0, 0.0), held: HashSet::new() }
+    }
+
+    //finger is at (x, y) on the screen, returns the directions that were only just pushed (so menus move one step, like with a key)
+    pub fn move_to(&mut self, x: f64, y: f64, window_height: f64) -> Vec<Action> {
+        self.enabled = true;        //a touch came in, so it's a touch device even if the browser didn't say so
+        let center_x = JOYSTICK_MARGIN + JOYSTICK_RADIUS;
+        let center_y = window_height - JOYSTICK_MARGIN - JOYSTICK_RADIUS;
+        let mut dx = (x - center_x) / JOYSTICK_RADIUS;
+        let mut dy = (y - center_y) / JOYSTICK_RADIUS;
+        let norm = (dx * dx + dy * dy).sqrt();
+        if norm > 1.0 {     //finger went past the edge, the knob stays on the edge
+            dx /= norm;
+            dy /= norm;
+        }
+        self.stick = (deadzone(dx), deadzone(dy));
+
+        let previous = std::mem::take(&mut self.held);
+        let (x, y) = self.stick;
+        for (pushed, action) in [(y < -STICK_PRESS, Action::MoveUp), (y > STICK_PRESS, Action::MoveDown), (x < -STICK_PRESS, Action::MoveLeft), (x > STICK_PRESS, Action::MoveRight)] {
+            if pushed {
+                self.held.insert(action);
+            }
+        }
+        ALL_ACTIONS
+            .iter()
+            .copied()
+            .filter(|action| self.held.contains(action) && !previous.contains(action))
+            .collect()
+    }
+
+    pub fn release(&mut self) {     //finger lifted, the knob jumps back to the center
+        self.stick = (0.0, 0.0);
+        self.held.clear();
+    }
+
+    pub fn held(&self, action: Action) -> bool {
+        self.held.contains(&action)
+    }
+}
@@ -0,0 +1,181 @@
+//študijski uspeh: which year you're in, every exam attempt and the grades/ECTS that come out of them
+
+use crate::models::exam::{Subject, ALL_SUBJECTS};
+
+pub const FINAL_YEAR: u32 = 3;      //after passing everything in 3. letnik you graduate
+
+//slovenian grading: below 50 % is a 5 (negativno), then every 10 % is one grade up
+pub fn grade(percent: u32) -> u32 {
+    match percent {
+        0..=49 => 5,
+        50..=59 => 6,
+        60..=69 => 7,
+        70..=79 => 8,
+        80..=89 => 9,
+        _ => 10,
+    }
+}
+
+pub struct ExamAttempt {        //one exam attempt, written or oral
+    pub day: u32,
+    pub subject: Subject,
+    pub oral: bool,
+    pub percent: u32,
+    pub grade: u32,
+}
+
+impl ExamAttempt {
+    pub fn new(day: u32, subject: Subject, oral: bool, percent: u32) -> Self {
+        ExamAttempt { day, subject, oral, percent, grade: grade(percent) }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.grade > 5
+    }
+}
+
+pub struct Transcript {
+    pub year: u32,      //current letnik
+    pub attempts: Vec<ExamAttempt>,     //in the order they happened
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        Transcript { year: 1, attempts: vec![] }
+    }
+
+    pub fn enrolled(&self) -> Vec<Subject> {        //subjects of the current year
+        ALL_SUBJECTS.iter().copied().filter(|s| s.year() == self.year).collect()
+    }
+
+    pub fn is_enrolled(&self, subject: Subject) -> bool {
+        subject.year() == self.year
+    }
+
+    fn best_grade(&self, subject: Subject, oral: bool) -> Option<u32> {
+        self.attempts
+            .iter()
+            .filter(|a| a.subject == subject && a.oral == oral && a.passed())
+            .map(|a| a.grade)
+            .max()
+    }
+
+    pub fn written_passed(&self, subject: Subject) -> bool {
+        self.best_grade(subject, false).is_some()
+    }
+
+    pub fn oral_passed(&self, subject: Subject) -> bool {
+        self.best_grade(subject, true).is_some()
+    }
+
+    pub fn attempt_count(&self, subject: Subject) -> usize {
+        self.attempts.iter().filter(|a| a.subject == subject).count()
+    }
+
+    //the grade that goes into the index: written only, or the rounded average of written and oral
+    pub fn final_grade(&self, subject: Subject) -> Option<u32> {
+        let written = self.best_grade(subject, false)?;
+        if !subject.has_oral() {
+            return Some(written);
+        }
+        let oral = self.best_grade(subject, true)?;
+        Some((written + oral).div_ceil(2))
+    }
+
+    pub fn subject_passed(&self, subject: Subject) -> bool {
+        self.final_grade(subject).is_some()
+    }
+
+    pub fn ready_for_oral(&self, subject: Subject) -> bool {        //profesor te sprejme samo, če si že naredil pisni del
+        self.is_enrolled(subject) && self.written_passed(subject) && !self.oral_passed(subject)
+    }
+
+    pub fn credits(&self) -> u32 {
+        ALL_SUBJECTS.iter().filter(|s| self.subject_passed(**s)).map(|s| s.ects()).sum()
+    }
+
+    pub fn total_credits(&self) -> u32 {
+        ALL_SUBJECTS.iter().map(|s| s.ects()).sum()
+    }
+
+    pub fn average(&self) -> Option<f64> {      //povprečna ocena of everything passed so far
+        let grades: Vec<u32> = ALL_SUBJECTS.iter().filter_map(|s| self.final_grade(*s)).collect();
+        if grades.is_empty() {
+            return None;
+        }
+        Some(grades.iter().sum::<u32>() as f64 / grades.len() as f64)
+    }
+
+    pub fn year_complete(&self) -> bool {
+        self.enrolled().iter().all(|s| self.subject_passed(*s))
+    }
+
+    pub fn advance_year(&mut self) -> bool {        //moves you to the next letnik once the current one is done (there's none after the last one)
+        if !self.year_complete() || self.year >= FINAL_YEAR {
+            return false;
+        }
+        self.year += 1;
+        true
+    }
+
+    pub fn graduated(&self) -> bool {
+        ALL_SUBJECTS.iter().all(|s| self.subject_passed(*s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pass(transcript: &mut Transcript, subject: Subject, percent: u32) {
+        transcript.attempts.push(ExamAttempt::new(1, subject, false, percent));
+        if subject.has_oral() {
+            transcript.attempts.push(ExamAttempt::new(1, subject, true, percent));
+        }
+    }
+
+    #[test]
+    fn grade_boundaries() {
+        assert_eq!(grade(0), 5);
+        assert_eq!(grade(49), 5);
+        assert_eq!(grade(50), 6);
+        assert_eq!(grade(69), 7);
+        assert_eq!(grade(70), 8);
+        assert_eq!(grade(89), 9);
+        assert_eq!(grade(90), 10);
+        assert_eq!(grade(100), 10);
+    }
+
+    #[test]
+    fn final_grade_rounds_the_average_up() {
+        let mut transcript = Transcript::new();
+        transcript.attempts.push(ExamAttempt::new(1, Subject::Fizika, false, 85));      //9
+        assert_eq!(transcript.final_grade(Subject::Fizika), None);      //still needs the oral
+        assert!(transcript.ready_for_oral(Subject::Fizika));
+        transcript.attempts.push(ExamAttempt::new(2, Subject::Fizika, true, 30));       //failed oral doesn't count
+        transcript.attempts.push(ExamAttempt::new(3, Subject::Fizika, true, 55));       //6
+        assert_eq!(transcript.final_grade(Subject::Fizika), Some(8));       //7.5
+        transcript.attempts.push(ExamAttempt::new(1, Subject::Analiza1, false, 40));
+        transcript.attempts.push(ExamAttempt::new(2, Subject::Analiza1, false, 72));
+        assert_eq!(transcript.final_grade(Subject::Analiza1), Some(8));     //no oral, the best written one
+        assert_eq!(transcript.attempt_count(Subject::Analiza1), 2);
+    }
+
+    #[test]
+    fn passing_every_year_graduates() {
+        let mut transcript = Transcript::new();
+        assert!(!transcript.advance_year());
+        for year in 1..=FINAL_YEAR {
+            assert_eq!(transcript.year, year);
+            for subject in transcript.enrolled() {
+                pass(&mut transcript, subject, 95);
+            }
+            assert!(transcript.year_complete());
+            assert_eq!(transcript.advance_year(), year < FINAL_YEAR);
+        }
+        assert_eq!(transcript.year, FINAL_YEAR);
+        assert!(transcript.graduated());
+        assert_eq!(transcript.credits(), transcript.total_credits());
+        assert_eq!(transcript.average(), Some(10.0));
+    }
+}
@@ -0,0 +1,140 @@
+//dialogue text shows up letter by letter, any key (or a click) shows the rest of it at once
+
+use crate::models::gamestate::DialogueNodes;
+use crate::models::interactable::NpcId;
+use crate::models::settings::Settings;
+
+const LETTERS_PER_BLIP: usize = 2;      //a blip on every letter is too much
+
+pub struct Typewriter {
+    line: Option<(NpcId, DialogueNodes)>,       //which line is being typed, a new one starts from the beginning
+    shown: usize,       //letters shown so far
+    ticks: u32,     //ticks since the last letter
+}
+
+impl Typewriter {
+    pub fn new() -> Self {
+        Typewriter { line: None, shown: 0, ticks: 0 }
+    }
+
+    fn start(&mut self, line: (NpcId, DialogueNodes)) {
+        if self.line != Some(line) {
+            self.line = Some(line);
+            self.shown = 0;
+            self.ticks = 0;
+        }
+    }
+
+    //called every tick while a dialogue is open, the speed depends on who's talking and the text speed setting
+    //gives back the pitch of a blip when one should play (Audio::blip plays it)
+    pub fn tick(&mut self, line: (NpcId, DialogueNodes), text: &str, settings: &Settings) -> Option<f32> {
+        self.start(line);
+        let letters = text.chars().count();
+        if self.shown >= letters {
+            return None;
+        }
+        let Some(ticks_per_letter) = settings.ticks_per_letter(line.0.ticks_per_letter()) else {
+            self.shown = letters;       //instant text (or reduced motion)
+            return None;
+        };
+        self.ticks += 1;
+        if self.ticks < ticks_per_letter {
+            return None;
+        }
+        self.ticks = 0;
+        self.shown += 1;
+
+        let letter = text.chars().nth(self.shown - 1).unwrap_or(' ');
+        (!letter.is_whitespace() && self.shown % LETTERS_PER_BLIP == 1).then(|| line.0.voice())
+    }
+
+    pub fn reset(&mut self) {       //a new conversation types out from the start, even if it opens with the same line as the last one
+        self.line = None;
+    }
+
+    pub fn skip(&mut self, line: (NpcId, DialogueNodes)) {
+        self.start(line);
+        self.shown = usize::MAX;
+    }
+
+    pub fn done(&self, line: (NpcId, DialogueNodes), text: &str) -> bool {
+        self.line == Some(line) && self.shown >= text.chars().count()
+    }
+
+    pub fn visible<'a>(&self, line: (NpcId, DialogueNodes), text: &'a str) -> &'a str {        //the part of the text that's already typed out
+        if self.line != Some(line) {
+            return "";      //the line only just changed and hasn't ticked yet
+        }
+        match text.char_indices().nth(self.shown) {
+            Some((end, _)) => &text[..end],
+            None => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::settings::TextSpeed;
+
+    const LINE: (NpcId, DialogueNodes) = (NpcId::Peteršič, DialogueNodes::Živjo);      //3 ticks per letter
+
+    #[test]
+    fn reveals_letters_at_the_speakers_speed() {
+        let (mut typewriter, settings) = (Typewriter::new(), Settings::default_settings());
+        assert_eq!(typewriter.visible(LINE, "Dober dan"), "");
+        for _ in 0..3 {
+            typewriter.tick(LINE, "Dober dan", &settings);
+        }
+        assert_eq!(typewriter.visible(LINE, "Dober dan"), "D");
+        for _ in 0..3 * 8 {
+            typewriter.tick(LINE, "Dober dan", &settings);
+        }
+        assert_eq!(typewriter.visible(LINE, "Dober dan"), "Dober dan");
+        assert!(typewriter.done(LINE, "Dober dan"));
+    }
+
+    #[test]
+    fn skip_shows_everything_and_a_new_line_starts_over() {
+        let (mut typewriter, settings) = (Typewriter::new(), Settings::default_settings());
+        typewriter.tick(LINE, "Čšž.", &settings);
+        typewriter.skip(LINE);
+        assert_eq!(typewriter.visible(LINE, "Čšž."), "Čšž.");
+        assert!(typewriter.done(LINE, "Čšž."));
+
+        let next = (NpcId::Ema, DialogueNodes::Ok);
+        assert_eq!(typewriter.visible(next, "Ok."), "");
+        typewriter.tick(next, "Ok.", &settings);
+        assert_eq!(typewriter.visible(next, "Ok."), "O");
+    }
+
+    #[test]
+    fn blips_on_every_other_letter() {
+        let (mut typewriter, settings) = (Typewriter::new(), Settings::default_settings());
+        let line = (NpcId::Ema, DialogueNodes::Živjo);      //a letter every tick
+        let blips: Vec<Option<f32>> = (0..6).map(|_| typewriter.tick(line, "Ojoj  a", &settings)).collect();
+        let voice = Some(NpcId::Ema.voice());
+        assert_eq!(blips, vec![voice, None, voice, None, None, None]);       //the 5th letter is a space
+        assert_eq!(typewriter.tick(line, "Ojoj  a", &settings), voice);
+    }
+
+    #[test]
+    fn reset_types_the_same_line_again() {
+        let (mut typewriter, settings) = (Typewriter::new(), Settings::default_settings());
+        typewriter.skip(LINE);
+        typewriter.reset();
+        assert_eq!(typewriter.visible(LINE, "Dober dan"), "");
+        for _ in 0..3 {
+            typewriter.tick(LINE, "Dober dan", &settings);
+        }
+        assert_eq!(typewriter.visible(LINE, "Dober dan"), "D");
+    }
+
+    #[test]
+    fn instant_text_shows_the_line_on_the_first_tick() {
+        let (mut typewriter, mut settings) = (Typewriter::new(), Settings::default_settings());
+        settings.text_speed = TextSpeed::Instant;
+        typewriter.tick(LINE, "Dober dan", &settings);
+        assert!(typewriter.done(LINE, "Dober dan"));
+    }
+}
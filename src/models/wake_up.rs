@@ -0,0 +1,80 @@
+//jutro po prečuti noči: the alarm is ringing and you have to click as fast as you can to get out of bed
+
+pub const ALL_NIGHTER_ATTENTION: i32 = 40;     //if you go past midnight with less attention than this, you overslept
+pub const CLICKS_TO_WAKE_UP: u32 = 20;
+pub const TICKS_PER_LATE_MINUTE: u32 = 6;       //while you're in bed the clock runs fast, ~10 in-game minutes per second
+pub const MAX_MINUTES_LATE: u32 = 3 * 60;       //at 11:00 you get up no matter what
+pub const DOZE_TICKS: u32 = 30;     //half a second without clicking and you start falling back asleep
+pub const RESULT_DELAY_TICKS: u32 = 60;     //so the last frantic clicks don't skip the result
+
+pub const BASE_ANXIETY: i32 = 40;       //you wake up stressed even if you're quick
+pub const MAX_ANXIETY: i32 = 90;
+
+pub struct WakeUp {
+    pub clicks: u32,        //how awake you are, CLICKS_TO_WAKE_UP means you're up
+    pub minutes_late: u32,
+    pub finished: bool,
+    pub confirmed: bool,        //clicked away the result screen
+    ticks: u32,     //ticks since the last late minute
+    idle_ticks: u32,        //ticks since the last click
+    finished_ticks: u32,
+}
+
+impl WakeUp {
+    pub fn new() -> Self {
+        WakeUp {
+            clicks: 0,
+            minutes_late: 0,
+            finished: false,
+            confirmed: false,
+            ticks: 0,
+            idle_ticks: 0,
+            finished_ticks: 0,
+        }
+    }
+
+    pub fn click(&mut self) {
+        if self.finished {
+            self.confirmed = self.can_continue();
+            return;
+        }
+        self.idle_ticks = 0;
+        self.clicks += 1;
+        if self.clicks >= CLICKS_TO_WAKE_UP {
+            self.finished = true;
+        }
+    }
+
+    //called on every Tick, returns true when another in-game minute went by in bed
+    pub fn tick(&mut self) -> bool {
+        if self.finished {
+            self.finished_ticks += 1;
+            return false;
+        }
+
+        self.idle_ticks += 1;
+        if self.idle_ticks >= DOZE_TICKS {      //you close your eyes again
+            self.idle_ticks = 0;
+            self.clicks = self.clicks.saturating_sub(1);
+        }
+
+        self.ticks += 1;
+        if self.ticks < TICKS_PER_LATE_MINUTE {
+            return false;
+        }
+        self.ticks = 0;
+        self.minutes_late += 1;
+        if self.minutes_late >= MAX_MINUTES_LATE {
+            self.finished = true;
+        }
+        true
+    }
+
+    pub fn can_continue(&self) -> bool {        //result screen was up long enough
+        self.finished && self.finished_ticks >= RESULT_DELAY_TICKS
+    }
+
+    pub fn anxiety(&self) -> i32 {      //starting anxiety for the day, every 2 minutes late is one more point
+        (BASE_ANXIETY + self.minutes_late as i32 / 2).min(MAX_ANXIETY)
+    }
+}
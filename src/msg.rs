@@ -1,12 +1,36 @@
+use crate::models::character::Character;
+use crate::models::gamestate::PauseOption;
+use crate::models::settings::SettingsRow;
+use crate::models::input::Action;
+use crate::models::interactable::Interactable;
+
 //we will list every kind of event/message the game can receive so when sth happens the right Msg variant is sent to "Update"
 #[derive(Clone)]
 pub enum Msg {
     KeyDown(String),    //a general event when any key is pressed down
     KeyUp(String),
+    Pressed(Action),        //a controller button (or the stick) was pressed, same as a key bound to that action
+    TouchStick(f64, f64),       //finger on the on-screen joystick, screen coordinates
+    TouchStickEnd,      //finger lifted off the joystick
     Tick,       //represents periodic update ex. one game frame (for animations, movement)
     StartPressed,       //when player clicks Start on start screen
+    SelectCharacter(Character),     //click on a character on the selection screen
     StartFinished,      //end of start screen to game transition
     Ignore,         //when sth happens you wanna ignore
     Menu,
+    Pause,      //Esc while walking around, also "back" from screens opened from the pause menu
+    SelectPauseOption(PauseOption),
+    ShowEndings,        //endings gallery button on the start screen
+    ShowAchievements,       //achievements button on the start screen
+    ShowSettings(bool),     //settings button on the start screen (false) or in the pause menu (true)
+    SelectSettingsRow(SettingsRow),     //click on a row of the settings screen
+    ChangeSetting(SettingsRow, i32),        //click on the < or > next to a value
+    RebindKey(Action, usize),       //click on a key slot, the next key pressed goes there
+    ResetKeyBindings,
     SelectDialogueOption(usize),
+    SkipDialogueText,       //click on the dialogue box while the text is still being typed out
+    ContinueMishap,     //click on the mishap popup
+    MiniGameClick,      //click anywhere on a mini-game screen
+    WorldClick(f64, f64),       //click on the floor, world coordinates
+    WalkTo(Interactable),       //click on an object or npc
 }
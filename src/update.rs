@@ -1,19 +1,51 @@
-use crate::models::gamestate::{GameState, InteractionState, Screen, DialogueNodes, DialogueOutcome, MenuOption};
-use crate::models::interactable::{Interactable, Objects, NpcId};
+use crate::models::gamestate::{GameState, InteractionState, Screen, DialogueOutcome, MenuOption, PauseOption, PAUSE_OPTIONS};
+use crate::models::settings::{SettingsRow, SETTINGS_ROWS};
+use crate::models::oral_exam::OralExam;
+use crate::models::mishap::Trigger;
+use crate::models::achievement::AchievementEvent;
+use crate::models::input::{self, Action};
 use crate::models::player;
+use crate::models::character::ALL_CHARACTERS;
 use crate::msg::Msg;
 use sauron::Cmd;
-use web_sys::{window, HtmlAudioElement};
-use wasm_bindgen::JsCast;
+use crate::models::audio::Sfx;
 
 //////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this function will decide how to react to msgs, depending on gamestate (which because of 'mut' we can also modify), and return a command
+    if matches!(msg, Msg::KeyDown(_) | Msg::Pressed(_) | Msg::StartPressed | Msg::WorldClick(..)) {
+        game_state.audio.unlock();      //the first press lets the browser play sound
+    }
+    if let Msg::Tick = msg {
+        let track = game_state.music_track();
+        game_state.audio.play_music(track);     //crossfades when the room changes or an exam starts
+        let anxiety = game_state.music_anxiety();
+        game_state.audio.tick(&game_state.settings, anxiety);        //louder and darker when you're anxious
+        game_state.tick_toasts();       //achievement toasts go away on every screen, not just while playing
+        //controller presses go through update like key presses, their Cmds (like the start transition) go out with the tick's
+        let pressed: Vec<Cmd<Msg>> = game_state.gamepad.poll().into_iter().map(|action| update(game_state, Msg::Pressed(action))).collect();
+        if !pressed.is_empty() {
+            return Cmd::batch(pressed.into_iter().chain([react(game_state, msg)]));
+        }
+    }
+    react(game_state, msg)
+}
+
+fn react(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //what every msg does to the game
     match msg {
-        Msg::StartPressed => {      //when you click Start, set gamestate screen to StartPressed
+        Msg::StartPressed => {      //when you click Start, you first pick who you play as
+            game_state.settings.apply_fullscreen();     //the first click we get, a saved fullscreen setting can only be applied now
+            game_state.screen = Screen::CharacterSelect { selection: ALL_CHARACTERS[0] };
+            Cmd::none()
+        }
+
+        Msg::SelectCharacter(character) => {        //after picking a character, set gamestate screen to StartPressed
             game_state.screen = Screen::StartPressed;
-            game_state.player.money = game_state.player.max_money;      //this resets the player's parameters after game over otherwise the game remembers how much money and anxiety you had at game over 
-            game_state.player.anxiety = 0;
+            game_state.reset_run(character);      //this resets the player's parameters (and the clock, npcs, room) after game over otherwise the game remembers how much money and anxiety you had at game over 
+            if game_state.settings.reduced_motion {     //no transition screen
+                game_state.screen = Screen::Playing;
+                return Cmd::none();
+            }
             
             //once executed async function:
             Cmd::once(async {       //async is used bc ex. sleep would freeze the entire browser, async pauses the task here, but keeps the app running
@@ -28,126 +60,422 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
         }
 
         Msg::Ignore => Cmd::none(),
+
+        Msg::ShowEndings => {
+            game_state.screen = Screen::Endings;
+            Cmd::none()
+        }
+
+        Msg::ShowAchievements => {
+            game_state.screen = Screen::Achievements;
+            Cmd::none()
+        }
+
+        Msg::ShowSettings(from_pause) => {
+            game_state.screen = Screen::Settings { selection: SettingsRow::MusicVolume, from_pause };
+            Cmd::none()
+        }
+
+        Msg::SelectSettingsRow(row) => {        //click on a row: toggles flip, buttons do their thing
+            if let Screen::Settings { from_pause, .. } = game_state.screen {
+                game_state.screen = Screen::Settings { selection: row, from_pause };
+                return update(game_state, Msg::Pressed(Action::Confirm));
+            }
+            Cmd::none()
+        }
+
+        Msg::ChangeSetting(row, step) => {      //the < > arrows next to a value
+            game_state.settings.change(row, step);
+            if let Screen::Settings { from_pause, .. } = game_state.screen {
+                game_state.screen = Screen::Settings { selection: row, from_pause };
+            }
+            Cmd::none()
+        }
+
+        Msg::KeyDown(_) | Msg::Pressed(_) if matches!(game_state.screen, Screen::Settings { .. }) => {
+            if let Screen::Settings { selection, from_pause } = game_state.screen {
+                let actions = game_state.bindings.pressed(&msg);
+                let index = SETTINGS_ROWS.iter().position(|row| *row == selection).unwrap_or(0);
+                let back = if from_pause { Msg::Pause } else { Msg::Menu };
+                if actions.contains(&Action::MoveUp) && index > 0 {
+                    game_state.screen = Screen::Settings { selection: SETTINGS_ROWS[index - 1], from_pause };
+                } else if actions.contains(&Action::MoveDown) && index + 1 < SETTINGS_ROWS.len() {
+                    game_state.screen = Screen::Settings { selection: SETTINGS_ROWS[index + 1], from_pause };
+                } else if actions.contains(&Action::MoveLeft) {
+                    game_state.settings.change(selection, -1);
+                } else if actions.contains(&Action::MoveRight) {
+                    game_state.settings.change(selection, 1);
+                } else if actions.contains(&Action::Confirm) || actions.contains(&Action::Interact) {
+                    match selection {
+                        SettingsRow::KeyBindings => game_state.screen = Screen::KeyBindings { waiting: None, from_pause },
+                        SettingsRow::Back => return update(game_state, back),
+                        row => game_state.settings.change(row, 1),
+                    }
+                } else if actions.contains(&Action::Cancel) {
+                    return update(game_state, back);
+                }
+            }
+            Cmd::none()
+        }
+
+        Msg::RebindKey(action, slot) => {
+            if let Screen::KeyBindings { waiting, .. } = &mut game_state.screen {
+                *waiting = Some((action, slot));
+            }
+            Cmd::none()
+        }
+
+        Msg::ResetKeyBindings => {
+            game_state.bindings.reset();
+            if let Screen::KeyBindings { waiting, .. } = &mut game_state.screen {
+                *waiting = None;
+            }
+            Cmd::none()
+        }
+
+        //remapping: the first key after clicking a slot goes there, Escape just cancels (so it can't be rebound by accident)
+        //with nothing waiting, Escape (or Cancel on a controller) goes back to the settings
+        Msg::KeyDown(_) | Msg::Pressed(_) if matches!(game_state.screen, Screen::KeyBindings { .. }) => {
+            let back = matches!(&msg, Msg::KeyDown(key) if key == "Escape") || game_state.bindings.pressed(&msg).contains(&Action::Cancel);
+            if let Screen::KeyBindings { waiting, from_pause } = game_state.screen {
+                match (waiting, &msg) {
+                    (Some((action, slot)), Msg::KeyDown(key)) => {
+                        if key != "Escape" {
+                            game_state.bindings.bind(action, slot, key);
+                        }
+                        game_state.screen = Screen::KeyBindings { waiting: None, from_pause };
+                    }
+                    (None, _) if back => return update(game_state, Msg::ShowSettings(from_pause)),
+                    _ => {}     //controller buttons can't be bound, they just wait for a key
+                }
+            }
+            Cmd::none()
+        }
+
+        //pause menu: the game underneath is frozen, only the screen size keeps being checked
+        Msg::Pause => {
+            game_state.screen = Screen::Paused { selection: PauseOption::Resume };
+            game_state.pressed_keys.clear();        //so you don't keep walking after you come back
+            Cmd::none()
+        }
+
+        Msg::SelectPauseOption(option) => {
+            game_state.choose_pause_option(option);
+            Cmd::none()
+        }
+
+        Msg::KeyDown(_) | Msg::Pressed(_) if matches!(game_state.screen, Screen::Paused { .. }) => {
+            if let Screen::Paused { selection } = game_state.screen {
+                let actions = game_state.bindings.pressed(&msg);
+                let index = PAUSE_OPTIONS.iter().position(|option| *option == selection).unwrap_or(0);
+                if actions.contains(&Action::MoveUp) && index > 0 {
+                    game_state.screen = Screen::Paused { selection: PAUSE_OPTIONS[index - 1] };
+                } else if actions.contains(&Action::MoveDown) && index + 1 < PAUSE_OPTIONS.len() {
+                    game_state.screen = Screen::Paused { selection: PAUSE_OPTIONS[index + 1] };
+                } else if actions.contains(&Action::Confirm) || actions.contains(&Action::Interact) {
+                    game_state.choose_pause_option(selection);
+                } else if actions.contains(&Action::Cancel) {
+                    game_state.screen = Screen::Playing;
+                }
+            }
+            Cmd::none()
+        }
+
+        //menus outside of a run were only clickable, this way you can also get through them with a controller
+        Msg::KeyDown(_) | Msg::Pressed(_) if matches!(game_state.screen, Screen::Start | Screen::CharacterSelect { .. } | Screen::GameOver(_) | Screen::Graduated | Screen::Endings | Screen::Achievements) => {
+            let actions = game_state.bindings.pressed(&msg);
+            let confirm = actions.contains(&Action::Confirm) || actions.contains(&Action::Interact);
+            let cancel = actions.contains(&Action::Cancel);
+            match game_state.screen {
+                Screen::Start if confirm => return update(game_state, Msg::StartPressed),
+                Screen::CharacterSelect { selection } => {
+                    let index = ALL_CHARACTERS.iter().position(|character| *character == selection).unwrap_or(0);
+                    if (actions.contains(&Action::MoveLeft) || actions.contains(&Action::MoveUp)) && index > 0 {
+                        game_state.screen = Screen::CharacterSelect { selection: ALL_CHARACTERS[index - 1] };
+                    } else if (actions.contains(&Action::MoveRight) || actions.contains(&Action::MoveDown)) && index + 1 < ALL_CHARACTERS.len() {
+                        game_state.screen = Screen::CharacterSelect { selection: ALL_CHARACTERS[index + 1] };
+                    } else if confirm {
+                        return update(game_state, Msg::SelectCharacter(selection));
+                    } else if cancel {
+                        return update(game_state, Msg::Menu);
+                    }
+                }
+                Screen::GameOver(_) | Screen::Graduated | Screen::Endings | Screen::Achievements if confirm || cancel => return update(game_state, Msg::Menu),
+                _ => {}
+            }
+            Cmd::none()
+        }
+
+        Msg::Tick if matches!(game_state.screen, Screen::Paused { .. }) => {
+            game_state.update_viewport();
+            Cmd::none()
+        }
         
         Msg::Menu => {
             game_state.screen = Screen::Start;        //immediately after getting the StartFinished msg, change gamestate.screen to Playing
             Cmd::none()
         }
 
-        //receiving keyboard input:
+        //a mishap popup stops everything (even the exam timer) until you click through it
+        Msg::ContinueMishap => {
+            game_state.continue_mishap();
+            Cmd::none()
+        }
+
+        //on-screen joystick: pushing it far enough in a direction is the same as pressing that arrow key
+        Msg::TouchStick(x, y) => {
+            for action in game_state.touch.move_to(x, y, game_state.window_height) {
+                let _ = update(game_state, Msg::Pressed(action));
+            }
+            Cmd::none()
+        }
+
+        Msg::TouchStickEnd => {
+            game_state.touch.release();
+            Cmd::none()
+        }
+
+        Msg::KeyDown(_) | Msg::Pressed(_) if game_state.active_mishap.is_some() => {
+            let actions = game_state.bindings.pressed(&msg);
+            if actions.contains(&Action::Confirm) || actions.contains(&Action::Interact) || actions.contains(&Action::Cancel) {
+                game_state.continue_mishap();
+            }
+            Cmd::none()
+        }
+
+        Msg::Tick if game_state.active_mishap.is_some() => {
+            game_state.update_viewport();
+            Cmd::none()
+        }
+
+        //while a mini-game is on screen it gets all the input instead of the player walking around
+        Msg::KeyDown(_) | Msg::KeyUp(_) | Msg::Pressed(_) | Msg::Tick | Msg::MiniGameClick if matches!(game_state.screen, Screen::MiniGame(_)) => {
+            update_minigame(game_state, msg)
+        }
+
+        Msg::MiniGameClick => Cmd::none(),
+
+        //click-to-move, only while walking around (not with a menu or popup open)
+        Msg::WorldClick(_, _) | Msg::WalkTo(_)
+            if !matches!(game_state.screen, Screen::Playing)
+                || game_state.active_mishap.is_some()
+                || !matches!(game_state.interaction_state, InteractionState::None) => Cmd::none(),
+
+        Msg::WorldClick(x, y) => {
+            game_state.walk_to(x, y);
+            Cmd::none()
+        }
+
+        Msg::WalkTo(interactable) => {
+            if game_state.player_near(interactable, 40.0) {     //already next to it, no need to walk
+                game_state.interact(interactable);
+            } else {
+                game_state.walk_up_to(interactable);
+            }
+            Cmd::none()
+        }
+
+        //receiving keyboard (and controller) input:
         Msg::KeyDown(_)
         | Msg::KeyUp(_)
+        | Msg::Pressed(_)
         | Msg::Tick => {
             //if game not in screen Playing, ignore all other events:
             if !matches!(game_state.screen, Screen::Playing) {
                 return Cmd::none();
             }
             match msg {
-                Msg::KeyDown(key) => {
-                    if !game_state.music_started {  //if music hasn't started yet, it finds the audio element and plays it
-                        if let Some(win) = window() {
-                            if let Some(doc) = win.document() {
-                                if let Some(el) = doc.get_element_by_id("bg-music") {   //audio element
-                                    if let Ok(audio) = el.dyn_into::<HtmlAudioElement>() {
-                                        let _ = audio.play();
-                                    }
-                                }
-                            }
-                        }
-                        game_state.music_started = true;
+                Msg::KeyDown(_) | Msg::Pressed(_) => {
+                    if let Msg::KeyDown(key) = &msg {
+                        game_state.pressed_keys.insert(input::normalize(key));     //normalized, so Caps Lock doesn't stop you from walking
                     }
-                    game_state.pressed_keys.insert(key.clone());
+                    let actions = game_state.bindings.pressed(&msg);        //what this key/button does, the rest of the code only looks at actions
                     
             //Handling the Menu:
-            if let InteractionState::MenuOpen { interactable, selection } =
-                &mut game_state.interaction_state {
-                let options = GameState::menu_options_for_item(*interactable);      //get the menu options for this interactable object or NPC
+            if let InteractionState::MenuOpen { interactable, selection } = game_state.interaction_state {
+                let options = game_state.menu_options_for_item(interactable);      //get the menu options for this interactable object or NPC
                 let current_index = options     //find the index of the current selection in the options
                     .iter()
-                    .position(|opt| opt == selection)
+                    .position(|opt| *opt == selection)
                     .unwrap_or(0);
 
-                match key.as_str() {
-                    "ArrowUp" | "w" | "W" => {
-                        if current_index > 0 {
-                            *selection = options[current_index - 1]; //move selection up
+                if actions.contains(&Action::MoveUp) && current_index > 0 {
+                    game_state.interaction_state = InteractionState::MenuOpen {
+                        interactable,
+                        selection: options[current_index - 1], //move selection up
+                    };
+                } else if actions.contains(&Action::MoveDown) && current_index + 1 < options.len() {
+                    game_state.interaction_state = InteractionState::MenuOpen {
+                        interactable,
+                        selection: options[current_index + 1], //move selection down
+                    };
+                } else if actions.contains(&Action::Confirm) {
+                    //close menu before applying the selection, so options like going into another room start clean
+                    game_state.interaction_state = InteractionState::None;
+
+                    //applying selection effects (choosing the option)
+                    match selection {
+                        MenuOption::Buy(item) => game_state.buy(item, false),
+                        MenuOption::BuyWithVoucher(item) => game_state.buy(item, true),
+                        MenuOption::WorkShift => game_state.work_shift(),
+                        MenuOption::SellBook => game_state.sell_book(),
+                        MenuOption::Smoke => game_state.smoke(),
+                        MenuOption::GoHome => {
+                            game_state.audio.play(Sfx::Door, &game_state.settings);
+                            game_state.go_home();
                         }
-                    }
-                    "ArrowDown" | "s" | "S" => {
-                        if current_index + 1 < options.len() {
-                            *selection = options[current_index + 1]; //move selection down
+                        MenuOption::GoToRoom(room) => {
+                            game_state.audio.play(Sfx::Door, &game_state.settings);
+                            game_state.enter_room(room);
+                            game_state.roll_mishaps(Trigger::EnterRoom);
                         }
+                        MenuOption::WriteExam(subject) => game_state.start_written_exam(subject),
                     }
-                    "Enter" => {
-                        //applying selection effects (choosing the option)
-                        match *selection {
-                            MenuOption::Coffee => game_state.buy_coffee(),
-                            MenuOption::Tortilla => game_state.buy_tortilla(),
-                            MenuOption::Smoke => game_state.smoke(),
-                            MenuOption::GoHome => game_state.go_home(),
-                        }
+                } else if actions.contains(&Action::Cancel) {
+                    //cancel menu whenever you press escape
+                    game_state.interaction_state = InteractionState::None;
+                }
 
-                        //close menu after selection
-                        game_state.interaction_state = InteractionState::None;
-                    }
-                    "Escape" => {
-                        //cancel menu whenever you press escape
-                        game_state.interaction_state = InteractionState::None;
+                return Cmd::none(); //stop movement while menu is open
+            }
+
+            //Handling dialogue, same as the menu (number keys pick a response directly):
+            if let InteractionState::Dialogue { npc, node, exam, selection } = game_state.interaction_state {
+                let line = game_state.dialogue_line(npc, node);
+                if !game_state.typewriter.done((npc, node), &line) {
+                    game_state.typewriter.skip((npc, node));        //the first key just shows the whole line
+                    return Cmd::none();
+                }
+
+                let count = game_state.response_count(npc, node);
+                let number = match &msg {
+                    Msg::KeyDown(key) => key.parse::<usize>().ok().filter(|n| (1..=count.min(9)).contains(n)),
+                    _ => None,
+                };
+
+                if let Some(n) = number {
+                    return update(game_state, Msg::SelectDialogueOption(n - 1));
+                } else if actions.contains(&Action::MoveUp) && selection > 0 {
+                    game_state.interaction_state = InteractionState::Dialogue { npc, node, exam, selection: selection - 1 };
+                } else if actions.contains(&Action::MoveDown) && selection + 1 < count {
+                    game_state.interaction_state = InteractionState::Dialogue { npc, node, exam, selection: selection + 1 };
+                } else if actions.contains(&Action::Confirm) || actions.contains(&Action::Interact) {
+                    return update(game_state, Msg::SelectDialogueOption(selection));
+                } else if actions.contains(&Action::Cancel) && exam.is_none() {
+                    game_state.interaction_state = InteractionState::None;      //you can walk away from a chat, but not from an exam
+                }
+
+                return Cmd::none(); //F doesn't start the dialogue over
+            }
+
+            //Handling the inventory screen:
+            if let InteractionState::Inventory { selection } = game_state.interaction_state {
+                let slot_count = game_state.player.inventory.slots.len();
+                if actions.contains(&Action::MoveUp) && selection > 0 {
+                    game_state.interaction_state = InteractionState::Inventory { selection: selection - 1 };
+                } else if actions.contains(&Action::MoveDown) && selection + 1 < slot_count {
+                    game_state.interaction_state = InteractionState::Inventory { selection: selection + 1 };
+                } else if actions.contains(&Action::Confirm) {
+                    if let Some(slot) = game_state.player.inventory.slots.get(selection) {
+                        game_state.use_item(slot.kind);
                     }
-                    _ => {}
+                    //if the last piece got used up the slot disappears, so we keep the selection inside the list
+                    let new_len = game_state.player.inventory.slots.len();
+                    game_state.interaction_state = InteractionState::Inventory { selection: selection.min(new_len.saturating_sub(1)) };
+                } else if actions.contains(&Action::Cancel) || actions.contains(&Action::Inventory) {
+                    game_state.interaction_state = InteractionState::None;
                 }
 
-                return Cmd::none(); //stop movement while menu is open
+                return Cmd::none(); //no walking around while looking into the backpack
             }
 
-            //Open interaction menu on 'f' or 'F':
-            if key.eq_ignore_ascii_case("f") {
+            //Transcript overlay, any of these keys closes it:
+            if let InteractionState::Transcript = game_state.interaction_state {
+                if actions.contains(&Action::Cancel) || actions.contains(&Action::Transcript) {
+                    game_state.interaction_state = InteractionState::None;
+                }
+                return Cmd::none();
+            }
+
+            //Quest log overlay, same as the transcript:
+            if let InteractionState::QuestLog = game_state.interaction_state {
+                if actions.contains(&Action::Cancel) || actions.contains(&Action::QuestLog) {
+                    game_state.interaction_state = InteractionState::None;
+                }
+                return Cmd::none();
+            }
+
+            //Pause (Esc by default), only when nothing else is open:
+            if actions.contains(&Action::Cancel) && matches!(game_state.interaction_state, InteractionState::None) {
+                return update(game_state, Msg::Pause);
+            }
+
+            //Open the quest log (Q by default):
+            if actions.contains(&Action::QuestLog) && matches!(game_state.interaction_state, InteractionState::None) {
+                game_state.interaction_state = InteractionState::QuestLog;
+                return Cmd::none();
+            }
+
+            //Open the transcript (T by default):
+            if actions.contains(&Action::Transcript) && matches!(game_state.interaction_state, InteractionState::None) {
+                game_state.interaction_state = InteractionState::Transcript;
+                return Cmd::none();
+            }
+
+            //Open the inventory (I by default, only when nothing else is open):
+            if actions.contains(&Action::Inventory) && matches!(game_state.interaction_state, InteractionState::None) {
+                game_state.interaction_state = InteractionState::Inventory { selection: 0 };
+                return Cmd::none();
+            }
+
+            //Open interaction menu (F by default):
+            if actions.contains(&Action::Interact) {
                 //only open menu if not already open
                 if !matches!(game_state.interaction_state, InteractionState::MenuOpen { .. }) {
                     //check if the player is near any interactable
                     if let Some(interactable) = game_state.player_near_item(40.0) {
-                        match interactable {
-                            Interactable::Object(obj) => match obj {
-                                Objects::Counter | Objects::Door => {
-                                    // Open menu for objects
-                                    let options = GameState::menu_options_for_item(interactable);
-                                    game_state.interaction_state = InteractionState::MenuOpen {
-                                        interactable,
-                                        selection: options[0], //start from the first option
-                                    };
-                                }
-                            },
-                            Interactable::Npc(npc) => match npc {
-                                NpcId::Ema => {
-                                    // If we're talking to the only NPC we have for now
-                                    game_state.interaction_state = InteractionState::Dialogue {
-                                        npc,               //store the NPC
-                                        node: DialogueNodes::Živjo, //start dialogue from Živjo
-                                    };
-                                    return Cmd::none(); //stop movement when dialogue starts
-                                }
-                            },
-                        }
+                        game_state.player.path.clear();     //you were walking somewhere with a click, but F came first
+                        game_state.player.walk_target = None;
+                        game_state.interact(interactable);
                     }
                 }
             }}
 
 
                 Msg::KeyUp(key) => {    //we need to remove the key when we stop holding it
-                    game_state.pressed_keys.remove(&key);
+                    game_state.pressed_keys.remove(&input::normalize(&key));
                 }
 
                 Msg::Tick => {
                     game_state.update_viewport();       //on every tick we check if the screen size changed
+                    let new_minute = game_state.clock.advance();     //time goes on
+                    game_state.check_new_day();     //morning stuff if the day changed
+                    game_state.tick_notification();
+                    game_state.update_npcs();       //npcs follow their schedules
+                    if let InteractionState::Dialogue { npc, node, .. } = game_state.interaction_state {
+                        let line = game_state.dialogue_line(npc, node);
+                        if let Some(pitch) = game_state.typewriter.tick((npc, node), &line, &game_state.settings) {
+                            game_state.audio.blip(pitch, &game_state.settings);
+                        }
+                    }
+                    if new_minute {
+                        game_state.check_quests();      //deadlines
+                    }
+                    if new_minute && matches!(game_state.interaction_state, InteractionState::None) {
+                        game_state.roll_mishaps(Trigger::Random);       //manjše nevšečnosti only happen while you walk around
+                    }
 
                     //we add the key to pressed_keys (so we can monitor for more than 1 key pressed at once)
-                    let left = game_state.pressed_keys.contains("ArrowLeft") || game_state.pressed_keys.contains("a");
-                    let right = game_state.pressed_keys.contains("ArrowRight") || game_state.pressed_keys.contains("d");
-                    let up = game_state.pressed_keys.contains("ArrowUp") || game_state.pressed_keys.contains("w");
-                    let down = game_state.pressed_keys.contains("ArrowDown") || game_state.pressed_keys.contains("s");
-
-                    let mut dx: f64 = 0.0;
-                    let mut dy: f64 = 0.0;
+                    let (bindings, pressed, gamepad, touch) = (&game_state.bindings, &game_state.pressed_keys, &game_state.gamepad, &game_state.touch);
+                    let talking = matches!(game_state.interaction_state, InteractionState::Dialogue { .. });      //arrows pick a response then, they don't walk you away
+                    let held = |action| !talking && (bindings.held(pressed, action) || gamepad.held(action) || touch.held(action));        //keyboard, controller or on-screen joystick
+                    let left = held(Action::MoveLeft);
+                    let right = held(Action::MoveRight);
+                    let up = held(Action::MoveUp);
+                    let down = held(Action::MoveDown);
+
+                    let stick = if talking { (0.0, 0.0) } else if touch.stick != (0.0, 0.0) { touch.stick } else { gamepad.stick };
+                    let (mut dx, mut dy) = if left || right || up || down { (0.0, 0.0) } else { stick };        //a stick tilted only a bit walks slower
                     if left {       //calculates the distance (if diagonal we still need to diagonalize it)
                         dx -= 1.0;
                     }
@@ -161,13 +489,29 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
                         dy += 1.0;
                     }
 
-                    if dx != 0.0 && dy != 0.0 {     //normalizing the distance if we are moving diagonally
-                        let norm = (dx * dx + dy * dy).sqrt();
+                    let speed = 5.0;        //setting the desired speed
+                    if dx != 0.0 || dy != 0.0 {     //the keys (or a stick) take over from click-to-move
+                        game_state.player.path.clear();
+                        game_state.player.walk_target = None;
+                    } else if let Some(&(tx, ty)) = game_state.player.path.first() {       //walking along the clicked path, one waypoint at a time
+                        let (ex, ey) = (tx - game_state.player.x, ty - game_state.player.y);
+                        let dist = (ex * ex + ey * ey).sqrt();
+                        if dist <= speed {      //close enough, this step lands exactly on the waypoint
+                            dx = ex / speed;
+                            dy = ey / speed;
+                            game_state.player.path.remove(0);
+                        } else {
+                            dx = ex / dist;
+                            dy = ey / dist;
+                        }
+                    }
+
+                    let norm = (dx * dx + dy * dy).sqrt();
+                    if norm > 1.0 {     //normalizing the distance if we are moving diagonally (or the stick is in a corner)
                         dx /= norm;
                         dy /= norm;     //so at this point player moves in all directions at speed 1
                     }
 
-                    let speed = 5.0;        //setting the desired speed
                     dx *= speed;
                     dy *= speed;
 
@@ -177,9 +521,15 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
                     let pw = game_state.player.width;
                     let ph = game_state.player.height;
 
-                    if !game_state.collides_with_wall(next_x, next_y, pw, ph) {
+                    //npcs can walk into you, so they only block you if you're not already standing inside one
+                    let blocked_by_npc = game_state.collides_with_npc(next_x, next_y, pw, ph)
+                        && !game_state.collides_with_npc(game_state.player.x, game_state.player.y, pw, ph);
+
+                    if !game_state.collides_with_wall(next_x, next_y, pw, ph) && !blocked_by_npc {
                         game_state.player.move_by(dx, dy); // premakneš se sam če ni stene tm kamor hočeš it
-                    }     
+                    } else {
+                        game_state.player.path.clear();     //someone is standing in the way of the clicked path, so we just stop
+                    }
 
                     //changing where player looks depending on movement:
                     if dx < 0.0 {
@@ -210,10 +560,18 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
 
                     //checking whether we're near enough to an interactive item:
                     game_state.nearby_item = game_state.player_near_item(40.0);     //change this threshold if you want it to activate closer/further
+
+                    //end of a clicked path: if you clicked on something, its menu or dialogue opens
+                    if game_state.player.path.is_empty() {
+                        if let Some(target) = game_state.player.walk_target.take() {
+                            if game_state.player_near(target, 40.0) && matches!(game_state.interaction_state, InteractionState::None) {
+                                game_state.interact(target);
+                            }
+                        }
+                    }
           
                     if game_state.player.anxiety >= game_state.player.max_anxiety {
-                        game_state.screen = Screen::GameOver;
-                        game_state.interaction_state = InteractionState::None;
+                        game_state.end_game(game_state.panic_ending());
                         return Cmd::none(); // konec igre če maxaš out anxiety stat
                     }
 /* 
@@ -233,15 +591,24 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
             Cmd::none()
          },
         
+        Msg::SelectDialogueOption(_) | Msg::SkipDialogueText if game_state.active_mishap.is_some() => Cmd::none(),
+
+        Msg::SkipDialogueText => {
+            if let InteractionState::Dialogue { npc, node, .. } = game_state.interaction_state {
+                game_state.typewriter.skip((npc, node));
+            }
+            Cmd::none()
+        }
+
         Msg::SelectDialogueOption(choice_index) => {
-            if let InteractionState::Dialogue { npc, node } =
-                &game_state.interaction_state
+            if let InteractionState::Dialogue { npc, node, mut exam, .. } =
+                game_state.interaction_state
             {
                 let dialogue = GameState::npc_dialogue(
-                    *npc,
+                    npc,
                 );
 
-                let current_node = match dialogue.get(node) {
+                let current_node = match dialogue.get(&node) {
                     Some(n) => n,
                     None => {
                         game_state.interaction_state = InteractionState::None;
@@ -249,24 +616,69 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
                     }
                 };
 
-                let response = &current_node.responses[choice_index];
+                //the index is counted only among the responses that are shown (some need items)
+                let visible: Vec<_> = current_node
+                    .responses
+                    .iter()
+                    .filter(|r| game_state.response_available(r))
+                    .collect();
+                let Some(response) = visible.get(choice_index) else {
+                    return Cmd::none();
+                };
+
+                game_state.apply_dialogue_effects(npc, &response.effects);
+                if let Some(exam) = &mut exam {
+                    exam.score += response.score;
+                }
 
                 match &response.outcome {
                     DialogueOutcome::Continue(next_node) => {
                         game_state.interaction_state = InteractionState::Dialogue {
-                            npc: *npc,
+                            npc,
                             node: *next_node,
+                            exam,
+                            selection: 0,
                         };
+                        game_state.achievement_event(AchievementEvent::ReachedNode(*next_node));
                     }
 
-                    DialogueOutcome::EndDialogue => {
+                    DialogueOutcome::StartOralExam(first_question) => {
+                        game_state.interaction_state = InteractionState::Dialogue {
+                            npc,
+                            node: *first_question,
+                            exam: Some(OralExam { professor: npc, score: 0 }),
+                            selection: 0,
+                        };
+                        game_state.exam_nerves();
+                        game_state.roll_mishaps(Trigger::OralExam);     //ex. the professor is late
+                    }
+
+                    DialogueOutcome::Gamble { chance, uses_meters, success, failure } => {
+                        let chance = game_state.gamble_chance(npc, *chance, *uses_meters);
+                        let next_node = if game_state.rng.chance(chance as f64 / 100.0) { *success } else { *failure };
+                        game_state.interaction_state = InteractionState::Dialogue {
+                            npc,
+                            node: next_node,
+                            exam,
+                            selection: 0,
+                        };
+                        game_state.achievement_event(AchievementEvent::ReachedNode(next_node));
+                    }
+
+                    DialogueOutcome::FinishOralExam => {
                         game_state.interaction_state = InteractionState::None;
+                        if let Some(exam) = exam {
+                            game_state.finish_oral_exam(exam);
+                        }
                     }
 
-                    DialogueOutcome::EndGame => {
-                        game_state.screen = Screen::GameOver;
+                    DialogueOutcome::EndDialogue => {
                         game_state.interaction_state = InteractionState::None;
                     }
+
+                    DialogueOutcome::EndGame(ending) => {
+                        game_state.end_game(*ending);
+                    }
                 }
             }
 
@@ -275,3 +687,50 @@ pub fn update(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {       //this f
     }
 }
 
+fn update_minigame(game_state: &mut GameState, msg: Msg) -> Cmd<Msg> {
+    if let Msg::Tick = msg {
+        game_state.update_viewport();
+    }
+    //we take the mini-game out of the screen for a moment, so it can change the rest of the game state
+    let mut game = match std::mem::replace(&mut game_state.screen, Screen::Playing) {
+        Screen::MiniGame(game) => game,
+        other => {
+            game_state.screen = other;
+            return Cmd::none();
+        }
+    };
+
+    game.update(&msg, game_state);
+    match game.result() {
+        Some(result) => game_state.finish_minigame(result),
+        None => game_state.screen = Screen::MiniGame(game),
+    }
+
+    Cmd::none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::character::Character;
+
+    #[test]
+    fn confirm_on_character_select_starts_the_run() {
+        let mut game_state = GameState::offline((1200.0, 600.0));
+        game_state.screen = Screen::CharacterSelect { selection: Character::Ema };
+        let _transition = update(&mut game_state, Msg::Pressed(Action::Confirm));       //sends StartFinished once the transition screen is over
+        assert!(matches!(game_state.screen, Screen::StartPressed));
+        assert_eq!(game_state.player.character, Character::Ema);
+        let _ = update(&mut game_state, Msg::StartFinished);
+        assert!(matches!(game_state.screen, Screen::Playing));
+    }
+
+    #[test]
+    fn confirm_with_reduced_motion_goes_straight_to_playing() {
+        let mut game_state = GameState::offline((1200.0, 600.0));
+        game_state.settings.reduced_motion = true;
+        game_state.screen = Screen::CharacterSelect { selection: Character::Lan };
+        let _ = update(&mut game_state, Msg::Pressed(Action::Confirm));
+        assert!(matches!(game_state.screen, Screen::Playing));
+    }
+}
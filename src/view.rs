@@ -1,13 +1,46 @@
-use crate::models::interactable::Interactable;
+use crate::models::interactable::{Interactable, Objects};
+use crate::models::inventory::ItemKind;
+use crate::models::shop::{Payment, DAILY_VOUCHERS};
+use crate::models::exam::ALL_SUBJECTS;
+use crate::models::quest::QuestStatus;
 use crate::msg::Msg;
-use crate::models::gamestate::{GameState, Screen, InteractionState};
+use crate::models::gamestate::{GameState, Screen, InteractionState, DialogueOutcome, PauseOption, PAUSE_OPTIONS};
+use crate::models::settings::{SettingsRow, SETTINGS_ROWS};
+use crate::models::oral_exam;
 use crate::models::player::Smer;
+use crate::models::character::{Character, ALL_CHARACTERS};
+use crate::models::ending::ALL_ENDINGS;
+use crate::models::achievement::ALL_ACHIEVEMENTS;
+use crate::models::input::{self, Action, ALL_ACTIONS, KEYS_PER_ACTION};
+use crate::models::touch::{JOYSTICK_MARGIN, JOYSTICK_RADIUS, TOUCH_BUTTONS};
+use web_sys::TouchEvent;
 use sauron::prelude::*;         //sauron library generates the HTML structure from the RUST code :)
 
 pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will describe what should be shown for the current Gamestate
+    let screen = match game_state.screen {
+        Screen::Start | Screen::GameOver(_) | Screen::Graduated | Screen::Endings | Screen::Achievements => keyboard_root(screen_view(game_state)),      //Enter and Esc work here too, not just clicks
+        _ => screen_view(game_state),
+    };
+    div([], [screen, toast_view(game_state)])      //achievement toasts can show up on any screen
+}
+
+fn keyboard_root(screen: Node<Msg>) -> Node<Msg> {      //same listener as the Playing screen, for menus that don't have their own
+    div(
+        [
+            on_keydown(|event: KeyboardEvent| Msg::KeyDown(event.key())),
+            on_keyup(|event: KeyboardEvent| Msg::KeyUp(event.key())),
+            attr("tabindex", "0"),
+            attr("id", "game-root"),
+            style! { "outline": "none" },
+        ],
+        [screen],
+    )
+}
+
+fn screen_view(game_state: &GameState) -> Node<Msg> {
     let player = &game_state.player;
 
-    match game_state.screen {
+    match &game_state.screen {
         Screen::Start => {      //renders when game is at start screen
             div(
                 [
@@ -47,6 +80,26 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                 ],
                 [],
             ),
+
+                //smaller buttons under the start button: endings gallery, achievements, key bindings
+                div(
+                    [
+                        style! {
+                            "position": "absolute",
+                            "left": "50%",
+                            "bottom": "4%",
+                            "transform": "translateX(-50%)",
+                            "display": "flex",
+                            "gap": "12px",
+                            "z-index": "10",
+                        },
+                    ],
+                    [
+                        start_menu_button(format!("Konci ({}/{})", game_state.endings.discovered.len(), ALL_ENDINGS.len()), Msg::ShowEndings),
+                        start_menu_button(format!("Dosežki ({}/{})", game_state.achievements.unlocked.len(), ALL_ACHIEVEMENTS.len()), Msg::ShowAchievements),
+                        start_menu_button(game_state.settings.language.pick("Nastavitve", "Settings").to_string(), Msg::ShowSettings(false)),
+                    ],
+                ),
                 ],
         )
         }
@@ -72,7 +125,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
             )
         }
 
-        Screen::Playing => {        //main playing screen where player first spawns (this is for now Mafija)
+        Screen::Playing | Screen::Paused { .. } => {        //main playing screen where player first spawns (this is for now Mafija), the pause menu goes on top of it
             let world_left = (game_state.window_width - game_state.viewport_width) / 2.0;       //calculating where the viewport starts so we can spawn the player there and also scale everything correctly
             let world_top  = (game_state.window_height - game_state.viewport_height) / 2.0;
 
@@ -96,7 +149,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                 [
                     // Money bar
                     {
-                        let ratio = player.money as f64 / player.max_money as f64; // kokšen del money bara je pobarvan 
+                        let ratio = (player.money as f64 / player.max_money as f64).min(1.0); // kokšen del money bara je pobarvan (you can earn more than max_money, the bar just stays full)
                         let total_width = 200.0;
                         let filled_width = total_width * ratio;
 
@@ -141,7 +194,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                                             pointer_events: "none"
                                         },
                                     ],
-                                    [text("denar")],
+                                    [text(format!("denar: {} €", player.money))],
                                 ),
                             ],
                         )
@@ -200,10 +253,153 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                         )
                     },
 
+                    //Attention bar
+                    {
+                        let ratio3 = player.attention as f64 / player.max_attention as f64;
+                        let total_width = 200.0;
+                        let filled_width = total_width * ratio3;
+
+                        div(
+                            [
+                                style! {
+                                    top: "90px"
+                                    left: "30px"
+                                    width: format!("{}px", total_width)
+                                    height: "20px"
+                                    background: "#0f3b3a"
+                                    border: "2px solid black"
+                                    z_index: 100
+                                    position: "relative"
+                                },
+                            ],
+                            [
+                                div(
+                                    [
+                                        style! {
+                                            width: format!("{}px", filled_width)
+                                            height: "100%"
+                                            background: "#35e0d0"
+                                        },
+                                    ],
+                                    [],
+                                ),
+                                div(
+                                    [
+                                        style! {
+                                            position: "absolute"
+                                            top: "0"
+                                            left: "0"
+                                            width: "100%"
+                                            height: "100%"
+                                            display: "flex"
+                                            align_items: "center"
+                                            justify_content: "center"
+                                            font_size: "12px"
+                                            font_family: "monospace"
+                                            color: "black"
+                                            pointer_events: "none"
+                                        },
+                                    ],
+                                    [text("pozornost")],
+                                ),
+                            ],
+                        )
+                    },
+
+                    //last few transactions under the bars, so you see what you earned and spent
+                    div(
+                        [
+                            style! {
+                                position: "absolute"
+                                top: "150px"
+                                left: "30px"
+                                font_size: "12px"
+                                font_family: "monospace"
+                                z_index: 100
+                            },
+                        ],
+                        game_state.transactions.iter().rev().take(3).map(|t| {
+                            div(
+                                [
+                                    style! {
+                                        color: if t.amount >= 0 { "#7dff7a" } else { "#ff7a7a" }
+                                        text_shadow: "1px 1px 0 black"
+                                    },
+                                ],
+                                [text(format!("D{} {:02}:{:02} {:+} € {}", t.day, t.minutes / 60, t.minutes % 60, t.amount, t.kind.label()))],
+                            )
+                        }),
+                    ),
+
+                    //Clock and room name
+                    div(
+                        [
+                            style! {
+                                position: "absolute"
+                                top: "30px"
+                                right: "30px"
+                                padding: "4px 10px"
+                                background: "#2b1d12"
+                                border: "2px solid black"
+                                color: "white"
+                                font_size: "14px"
+                                font_family: "monospace"
+                                z_index: 100
+                            },
+                        ],
+                        [text(format!("{} | {}", game_state.clock.label(), game_state.current_room.name()))],
+                    ),
+
+                    //letnik and credits under the clock, plus the last exam attempt
+                    div(
+                        [
+                            style! {
+                                position: "absolute"
+                                top: "60px"
+                                right: "30px"
+                                text_align: "right"
+                                font_size: "12px"
+                                font_family: "monospace"
+                                color: "white"
+                                text_shadow: "1px 1px 0 black"
+                                z_index: 100
+                            },
+                        ],
+                        [
+                            div([], [text(format!(
+                                "{}. letnik | {}/{} ECTS ({} = indeks)",
+                                game_state.transcript.year,
+                                game_state.transcript.credits(),
+                                game_state.transcript.total_credits(),
+                                game_state.bindings.label(Action::Transcript)
+                            ))]),
+                            match game_state.transcript.attempts.last() {
+                                Some(attempt) => div(
+                                    [style! { color: if attempt.passed() { "#7dff7a" } else { "#ff7a7a" } }],
+                                    [text(format!(
+                                        "D{} {} ({}): {} % - ocena {}",
+                                        attempt.day,
+                                        attempt.subject.name(),
+                                        if attempt.oral { "ustni" } else { "pisni" },
+                                        attempt.percent,
+                                        attempt.grade
+                                    ))],
+                                ),
+                                None => div([], []),
+                            },
+                        ],
+                    ),
+
                     //Rendering the interactive items:
                     // ================= WORLD CONTAINER =================
                     div(
                         [
+                            {       //click-to-move: screen position -> world coordinates
+                                let scale = game_state.scale;
+                                on_click(move |event: MouseEvent| {
+                                    Msg::WorldClick((event.client_x() as f64 - world_left) / scale, (event.client_y() as f64 - world_top) / scale)
+                                })
+                            },
                             style! {
                                 "position": "absolute",
                                 "left": format!("{}px", world_left),
@@ -217,7 +413,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                             // Background
                             img(
                                 vec![       //vector because the background is already a child and if we want to add ex. table separately, we can do it beside (on top of) the background by defining it as another vector :)
-                                    attr("src", "/static/background/mafija_1.png"),
+                                    attr("src", game_state.current_room.background()),
                                     style! {
                                         "position" : "absolute",
                                         "top": "0px",
@@ -230,30 +426,46 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                                 ],
                                 vec![],
                             ),
-                            // npc
-                                 img(
-                                    [
-                                        attr("src", "/static/characters/ema_naravnost_2.png",),
-                                        style! {
-                                            "position": "absolute",
-                                            "left": format!("{}px", 530.0 * game_state.scale),      //og dimenzije so 24 x 62
-                                            "top": format!("{}px", 450.0 * game_state.scale),   
-                                            "width": format!("{}px", 24.0 * 1.5 *  game_state.scale),  
-                                            "height": format!("{}px", 62.0 * 1.5 * game_state.scale), 
-                                            "z-index": "9",                                     
-                                            "image-rendering": "pixelated",
-                                        },
-                                    ],
-                                    vec![], 
-                                ),
+                            // npcs that are currently in the same room as the player
+                            div(
+                                [],
+                                game_state.npcs.iter().filter(|npc| npc.room == game_state.current_room).map(|npc| {
+                                    let id = npc.id;
+                                    img(
+                                        [
+                                            on_click(move |event: MouseEvent| {
+                                                event.stop_propagation();       //so the world doesn't also get the click
+                                                Msg::WalkTo(Interactable::Npc(id))
+                                            }),
+                                            attr("src", npc.sprite()),
+                                            style! {
+                                                "position": "absolute",
+                                                "left": format!("{}px", npc.x * game_state.scale),
+                                                "top": format!("{}px", npc.y * game_state.scale),
+                                                "width": format!("{}px", npc.width * game_state.scale),
+                                                "height": format!("{}px", npc.height * game_state.scale),
+                                                "z-index": "9",
+                                                "object-fit": "cover",      //placeholder sprites aren't cut to size yet
+                                                "image-rendering": "pixelated",
+                                            },
+                                        ],
+                                        [],
+                                    )
+                                }),
+                            ),
 
                             // Rendering the interactive items:
                             {// Interactive item hitboxes (invisible)
                                 div(
                                     [],
                                     game_state.interactive_items.iter().map(|item| {
+                                        let kind = item.kind;
                                         div(
                                             [
+                                                on_click(move |event: MouseEvent| {
+                                                    event.stop_propagation();
+                                                    Msg::WalkTo(kind)
+                                                }),
                                                 style! {
                                                     "position": "absolute",
                                                     "left": format!("{}px", item.x * game_state.scale),
@@ -276,11 +488,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
 
                             // Player
                             {
-                                let src = match player.smer {       //we want to add different images depending on where player is facing
-                                    Smer::Levo => "/static/characters/lan_levo_4x.png",
-                                    Smer::Desno => "/static/characters/lan_desno_4x.png",
-                                    Smer::Stoji => "/static/characters/lan_naravnost_4x.png",
-                                };
+                                let src = player.character.sprite(player.smer);       //we want to add different images depending on who you play and where they're facing
 
                                 img(
                                     [
@@ -300,21 +508,12 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                             },
 
                             //Press F prompt
-                            if let Some(interactable) = game_state.nearby_item {
-                                //find the coordinates of the interactable, whether object or npc
-                                let item = match interactable {
-                                    Interactable::Object(obj) => game_state
-                                        .interactive_items
-                                        .iter()
-                                        .find(|i| i.kind == Interactable::Object(obj))
-                                        .unwrap(),
-                                    Interactable::Npc(npc) => game_state
-                                        .interactive_items
-                                        .iter()
-                                        .find(|i| i.kind == Interactable::Npc(npc))
-                                        .unwrap(),
-                                };
-
+                            //find the coordinates of the interactable, whether object or npc (npcs move so we have to look them up every frame)
+                            //nearby_item can be a tick old (an npc just left the room, a save was loaded), then there's nothing to show
+                            if let Some(item) = game_state
+                                .nearby_item
+                                .and_then(|interactable| game_state.items_in_room().into_iter().find(|i| i.kind == interactable))
+                            {
                                 img(
                                     [
                                         attr("src", "/static/background/interactive_objects/F.png"),
@@ -338,7 +537,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                     ),
 
                     //npc dialogue
-                    if let InteractionState::Dialogue { npc, node } = &game_state.interaction_state {
+                    if let InteractionState::Dialogue { npc, node, exam, selection } = &game_state.interaction_state {
                     let dialogue = GameState::npc_dialogue(*npc);
                     let current_node = match dialogue.get(node) {
                             Some(n) => n,
@@ -348,34 +547,114 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                             }
                         };
 
+                    let line = game_state.dialogue_line(*npc, *node);
+                    let typed = game_state.typewriter.done((*npc, *node), &line);      //responses only show up once the whole line is there
+
                     div(
-                        [style! {
-                            "position": "absolute",
-                            "bottom": "20px",
-                            "left": "50%",
-                            "transform": "translateX(-50%)",
-                            "width": "600px",
-                            "background": "#222",
-                            "color": "white",
-                            "padding": "12px",
-                            "z-index": "100",
-                        }],
                         [
-                            div([], [text(current_node.text)]),
+                            on_click(|_| Msg::SkipDialogueText),
+                            style! {
+                                "position": "absolute",
+                                "bottom": "20px",
+                                "left": "50%",
+                                "transform": "translateX(-50%)",
+                                "width": "600px",
+                                "background": "#222",
+                                "color": "white",
+                                "padding": "12px",
+                                "z-index": "100",
+                            },
+                        ],
+                        [
+                            //during an oral exam the professor's name and the score track are shown above the question
+                            match (exam, oral_exam::professor(*npc)) {
+                                (Some(exam), Some(professor)) => div(
+                                    [style! { "font-size": "12px", "margin-bottom": "8px", "color": "#ccc" }],
+                                    [
+                                        div([], [text(format!("Ustni izpit: {} ({})", professor.subject.name(), professor.name))]),
+                                        div(
+                                            [style! { "margin-top": "4px", "width": "100%", "height": "8px", "background": "#555" }],
+                                            [div(
+                                                [style! {
+                                                    "width": format!("{}%", (exam.score.max(0) * 50 / professor.pass_score).min(100)),
+                                                    "height": "100%",
+                                                    "background": if exam.score >= professor.pass_score { "#4caf50" } else { "#e0a030" },
+                                                }],
+                                                [],
+                                            )],
+                                        ),
+                                        div([], [text(format!("Točke: {} (za pozitivno {})", exam.score, professor.pass_score))]),
+                                    ],
+                                ),
+                                //outside of exams you see how the npc feels about you
+                                _ => div(
+                                    [style! { "font-size": "12px", "margin-bottom": "8px", "color": "#ccc" }],
+                                    [text(format!("{} | odnos: {} ({})", npc.name(), game_state.affinity.label(*npc), game_state.affinity.get(*npc)))],
+                                ),
+                            },
+                            //portrait on the left, name plate and the text being typed out on the right
+                            div(
+                                [style! { "display": "flex", "gap": "12px", "align-items": "flex-start" }],
+                                [
+                                    img(
+                                        [
+                                            attr("src", npc.portrait()),
+                                            style! {
+                                                "width": "72px",
+                                                "height": "72px",
+                                                "flex-shrink": "0",
+                                                "object-fit": "cover",
+                                                "object-position": "top",        //just the head
+                                                "background": "#d5b18c",
+                                                "border": "3px solid black",
+                                                "image-rendering": "pixelated",
+                                            },
+                                        ],
+                                        [],
+                                    ),
+                                    div(
+                                        [],
+                                        [
+                                            div(
+                                                [style! {
+                                                    "display": "inline-block",
+                                                    "background": "#2b1d12",
+                                                    "border": "2px solid black",
+                                                    "color": "#ffdd35",
+                                                    "font-family": "monospace",
+                                                    "padding": "2px 8px",
+                                                    "margin-bottom": "6px",
+                                                }],
+                                                [text(npc.name())],
+                                            ),
+                                            div([style! { "min-height": "40px" }], [text(game_state.typewriter.visible((*npc, *node), &line))]),
+                                        ],
+                                    ),
+                                ],
+                            ),
                             div(
                                 [],
-                                current_node.responses.iter().enumerate().map(|(i, r)| {
+                                current_node.responses.iter().filter(|r| typed && game_state.response_available(r)).enumerate().map(|(i, r)| {
+                                    let label = match r.outcome {       //risky answers show how likely they are to work out
+                                        DialogueOutcome::Gamble { chance, uses_meters, .. } => format!("{} ({} %)", game_state.say(r.text), game_state.gamble_chance(*npc, chance, uses_meters)),
+                                        _ => game_state.say(r.text),
+                                    };
+                                    let selected = i == *selection;     //highlighted like the item menu
                                     div(
                                         [
-                                            on_click(move |_| Msg::SelectDialogueOption(i)),
+                                            on_click(move |event: MouseEvent| {
+                                                event.stop_propagation();       //the box underneath would skip the text
+                                                Msg::SelectDialogueOption(i)
+                                            }),
                                             style! {
                                                 "margin-top": "8px",
                                                 "cursor": "pointer",
-                                                "background": "#444",
-                                                "padding": "6px",
+                                                "padding": "6px 10px",
+                                                "background": if selected { "#ffdd35" } else { "#444" },
+                                                "color": if selected { "black" } else { "white" },
                                             },
                                         ],
-                                        [text(r.text)],
+                                        [text(if i < 9 { format!("{}. {}", i + 1, label) } else { label })],
                                     )
                                 }),
                             ),
@@ -391,7 +670,36 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                         selection,
                     } = &game_state.interaction_state
                     {
-                        let options = GameState::menu_options_for_item(*interactable);
+                        let options = game_state.menu_options_for_item(*interactable);
+
+                        //the counter also shows what you can pay with and what you already bought today
+                        let header = if *interactable == Interactable::Object(Objects::Counter) {
+                            let today: Vec<String> = game_state
+                                .purchases
+                                .iter()
+                                .filter(|p| p.day == game_state.clock.day)
+                                .map(|p| match p.payment {
+                                    Payment::Money(price) => format!("{:02}:{:02} {} ({} €)", p.minutes / 60, p.minutes % 60, p.item.name(), price),
+                                    Payment::Voucher => format!("{:02}:{:02} {} (bon)", p.minutes / 60, p.minutes % 60, p.item.name()),
+                                    Payment::Treat(npc) => format!("{:02}:{:02} {} (časti {})", p.minutes / 60, p.minutes % 60, p.item.name(), npc.name()),
+                                })
+                                .collect();
+
+                            div(
+                                [style! { "margin-bottom": "10px", "font-size": "12px" }],
+                                [
+                                    div([], [text(format!(
+                                        "Denar: {} € | Boni: {}/{}",
+                                        player.money,
+                                        player.inventory.count(ItemKind::BonZaTortiljo),
+                                        DAILY_VOUCHERS
+                                    ))]),
+                                    div([], [text(if today.is_empty() { game_state.say("Danes še nisi nič [kupil|kupila].") } else { format!("Danes: {}", today.join(", ")) })]),
+                                ],
+                            )
+                        } else {
+                            div([], [])
+                        };
 
                         div(
                             [
@@ -409,7 +717,7 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                                     "color": "white",
                                 },
                             ],
-                            options.iter().map(|option| {
+                            std::iter::once(header).chain(options.iter().map(|option| {
                                 let selected = option == selection;
 
                                 div(
@@ -423,22 +731,78 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                                     ],
                                     [text(option.label())],
                                 )
-                            }),
+                            })),
                         )
                     } else {
                         div([], [])
-                    }
+                    },
+
+                    //notification popup (refusals at the counter, new day...)
+                    if let Some(notification) = &game_state.notification {
+                        div(
+                            [
+                                style! {
+                                    "position": "absolute",
+                                    "top": "20px",
+                                    "left": "50%",
+                                    "transform": "translateX(-50%)",
+                                    "background": "#2b1d12",
+                                    "border": "2px solid black",
+                                    "color": "#ffdd35",
+                                    "padding": "8px 14px",
+                                    "font-family": "monospace",
+                                    "z-index": "150",
+                                },
+                            ],
+                            [text(&notification.text)],
+                        )
+                    } else {
+                        div([], [])
+                    },
+
+                    //inventory screen
+                    if let InteractionState::Inventory { selection } = &game_state.interaction_state {
+                        inventory_view(game_state, *selection)
+                    } else {
+                        div([], [])
+                    },
+
+                    //indeks
+                    if let InteractionState::Transcript = &game_state.interaction_state {
+                        transcript_view(game_state)
+                    } else {
+                        div([], [])
+                    },
+
+                    //naloge
+                    if let InteractionState::QuestLog = &game_state.interaction_state {
+                        quest_log_view(game_state)
+                    } else {
+                        div([], [])
+                    },
+
+                    mishap_view(game_state),
+                    touch_controls_view(game_state),
+                    pause_view(game_state),
                 ],
             )
         },
-        Screen::GameOver =>            
+        Screen::MiniGame(game) => div([], [game.view(game_state), mishap_view(game_state)]),     //a mishap can happen right before a mini-game (ex. the exam)
+        Screen::CharacterSelect { selection } => character_select_view(*selection),
+        Screen::Graduated => graduated_view(game_state),
+        Screen::Endings => endings_view(game_state),
+        Screen::Achievements => achievements_view(game_state),
+        Screen::Settings { selection, from_pause } => settings_view(game_state, *selection, *from_pause),
+        Screen::KeyBindings { waiting, from_pause } => key_bindings_view(game_state, *waiting, *from_pause),
+
+        Screen::GameOver(ending) =>            
         div(
                 [
                     attr("style", "width: 100vw; height: 100vh; overflow: hidden;"),        //prekopiran iz start screena, glej komentarje tam
                 ],
                 [img(
                     [
-                        attr("src", "/static/background/game_over_screen.png"),        
+                        attr("src", ending.art()),        //every ending has its own picture
                         style! {
                             "width": "100%",
                             "height": "100%",
@@ -453,6 +817,33 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
                     [],
                 ),
 
+                //which ending it was, on top of the picture
+                div(
+                    [
+                        style! {
+                            "position": "absolute",
+                            "left": "50%",
+                            "top": "8%",
+                            "transform": "translateX(-50%)",
+                            "background": "#2b1d12",
+                            "border": "3px solid black",
+                            "padding": "16px",
+                            "width": "460px",
+                            "color": "white",
+                            "font-family": "monospace",
+                            "z-index": "10",
+                        },
+                    ],
+                    [
+                        div([style! { "color": "#ffdd35", "font-size": "20px", "margin-bottom": "8px" }], [text(format!("Konec: {}", ending.title()))]),
+                        div([], [text(game_state.say(ending.text()))]),
+                        div(
+                            [style! { "margin-top": "10px", "font-size": "12px", "color": "#ccc" }],
+                            [text(format!("Odkritih koncev: {}/{}", game_state.endings.discovered.len(), ALL_ENDINGS.len()))],
+                        ),
+                    ],
+                ),
+
                 //Press To Start Over image on top of the background
                 img(
                 [
@@ -477,3 +868,762 @@ pub fn view(game_state: &GameState) -> Node<Msg> {      //this function will des
 
     }
 
+fn inventory_view(game_state: &GameState, selection: usize) -> Node<Msg> {     //list of everything in the backpack, styled like the item menu
+    let slots = &game_state.player.inventory.slots;
+
+    let rows: Vec<Node<Msg>> = if slots.is_empty() {
+        vec![div([], [text("Nahrbtnik je prazen.")])]
+    } else {
+        slots.iter().enumerate().map(|(i, slot)| {
+            let selected = i == selection;
+
+            div(
+                [
+                    style! {
+                        "display": "flex",
+                        "align-items": "center",
+                        "gap": "10px",
+                        "padding": "6px 10px",
+                        "margin-bottom": "4px",
+                        "background": if selected { "#ffdd35" } else { "transparent" },
+                        "color": if selected { "black" } else { "white" },
+                    },
+                ],
+                [
+                    match slot.kind.icon() {
+                        Some(src) => img(
+                            [
+                                attr("src", src),
+                                style! {
+                                    "width": "32px",
+                                    "height": "24px",
+                                    "image-rendering": "pixelated",
+                                },
+                            ],
+                            [],
+                        ),
+                        None => div([style! { "width": "32px" }], []),
+                    },
+                    div(
+                        [],
+                        [
+                            div([], [text(format!("{} x{}", slot.kind.name(), slot.count))]),
+                            div([style! { "font-size": "11px" }], [text(slot.kind.description())]),
+                        ],
+                    ),
+                ],
+            )
+        }).collect()
+    };
+
+    div(
+        [
+            style! {
+                "position": "absolute",
+                "left": "50%",
+                "top": "50%",
+                "transform": "translate(-50%, -50%)",
+                "background": "#2b1d12",
+                "border": "3px solid black",
+                "padding": "16px",
+                "z-index": "50",
+                "min-width": "320px",
+                "font-family": "monospace",
+                "color": "white",
+            },
+        ],
+        [
+            div([style! { "margin-bottom": "10px" }], [text(format!("Nahrbtnik ({} = uporabi, {} = zapri)", game_state.bindings.label(Action::Confirm), game_state.bindings.label(Action::Inventory)))]),
+            div([], rows),
+        ],
+    )
+}
+
+fn transcript_view(game_state: &GameState) -> Node<Msg> {       //indeks: one row per subject, same box as the backpack
+    let transcript = &game_state.transcript;
+    let rows = ALL_SUBJECTS.iter().map(|subject| {
+        let status = match transcript.final_grade(*subject) {
+            Some(grade) => format!("ocena {}", grade),
+            None if subject.has_oral() && transcript.written_passed(*subject) => "manjka ustni".to_string(),
+            None if subject.year() > transcript.year => "še ni vpisan".to_string(),
+            None => "ni opravljen".to_string(),
+        };
+        div(
+            [
+                style! {
+                    "display": "flex",
+                    "justify-content": "space-between",
+                    "gap": "20px",
+                    "padding": "4px 10px",
+                    "color": if transcript.subject_passed(*subject) { "#7dff7a" } else if transcript.is_enrolled(*subject) { "white" } else { "#888" },
+                },
+            ],
+            [
+                div([], [text(format!("{}. {} ({} ECTS)", subject.year(), subject.name(), subject.ects()))]),
+                div([], [text(format!("{} | poskusi: {}", status, transcript.attempt_count(*subject)))]),
+            ],
+        )
+    });
+
+    let average = match transcript.average() {
+        Some(avg) => format!("Povprečje: {:.2}", avg),
+        None => "Povprečje: -".to_string(),
+    };
+
+    div(
+        [
+            style! {
+                "position": "absolute",
+                "left": "50%",
+                "top": "50%",
+                "transform": "translate(-50%, -50%)",
+                "background": "#2b1d12",
+                "border": "3px solid black",
+                "padding": "16px",
+                "z-index": "50",
+                "min-width": "420px",
+                "font-family": "monospace",
+                "color": "white",
+            },
+        ],
+        [
+            div([style! { "margin-bottom": "10px" }], [text(format!("Indeks - {}. letnik ({} = zapri)", transcript.year, game_state.bindings.label(Action::Transcript)))]),
+            div([], rows),
+            div(
+                [style! { "margin-top": "10px" }],
+                [text(format!("ECTS: {}/{} | {}", transcript.credits(), transcript.total_credits(), average))],
+            ),
+        ],
+    )
+}
+
+fn quest_log_view(game_state: &GameState) -> Node<Msg> {     //every quest with its objectives, done ones crossed off, same box as the indeks
+    let quests = &game_state.quests;
+    let entries: Vec<Node<Msg>> = if quests.quests.is_empty() {
+        vec![div([], [text("Nimaš še nobene naloge.")])]
+    } else {
+        quests.quests.iter().map(|quest| {
+            let (status, color) = match quest.status {
+                QuestStatus::Active => ("v teku", "white"),
+                QuestStatus::Completed => ("opravljena", "#7dff7a"),
+                QuestStatus::Failed => ("ni uspela", "#ff5a35"),
+            };
+            let objectives = quest.objectives.iter().enumerate().map(|(i, objective)| {
+                let (mark, objective_color) = if i < quest.step {
+                    ("[x]", "#888")
+                } else if i == quest.step && quest.status == QuestStatus::Active {
+                    ("[ ]", "#ffdd35")
+                } else {
+                    ("[ ]", "#888")
+                };
+                div([style! { "padding-left": "14px", "color": objective_color }], [text(format!("{} {}", mark, objective.label()))])
+            });
+            let rewards: Vec<String> = quest.id.rewards().iter().map(|reward| reward.label()).collect();
+
+            div(
+                [style! { "margin-bottom": "12px" }],
+                [
+                    div([style! { "color": color }], [text(format!("{} ({})", quest.id.title(), status))]),
+                    div([style! { "font-size": "12px", "color": "#ccc", "margin-bottom": "4px" }], [text(quest.id.description())]),
+                    div([], objectives),
+                    div([style! { "font-size": "12px", "color": "#ccc", "margin-top": "4px" }], [text(format!("Nagrada: {}", rewards.join(", ")))]),
+                ],
+            )
+        }).collect()
+    };
+
+    div(
+        [
+            style! {
+                "position": "absolute",
+                "left": "50%",
+                "top": "50%",
+                "transform": "translate(-50%, -50%)",
+                "background": "#2b1d12",
+                "border": "3px solid black",
+                "padding": "16px",
+                "z-index": "50",
+                "width": "460px",
+                "font-family": "monospace",
+                "color": "white",
+            },
+        ],
+        [
+            div([style! { "margin-bottom": "10px" }], [text(format!("Naloge ({} = zapri)", game_state.bindings.label(Action::QuestLog)))]),
+            div([], entries),
+        ],
+    )
+}
+
+fn mishap_view(game_state: &GameState) -> Node<Msg> {     //nevšečnost popup, one page at a time, the effects are listed on the last one
+    let Some(active) = &game_state.active_mishap else {
+        return div([], []);
+    };
+    let mishap = &game_state.mishaps[active.index];
+    let last_page = active.page + 1 >= mishap.pages.len();
+    let page = mishap.pages.get(active.page).map(String::as_str).unwrap_or("");
+
+    let effects = if last_page && !mishap.effects.is_empty() {
+        let labels: Vec<String> = mishap.effects.iter().map(|effect| game_state.say(&effect.label())).collect();
+        div([style! { "margin-top": "12px", "font-size": "12px", "color": "#ff9a7a" }], [text(labels.join(" | "))])
+    } else {
+        div([], [])
+    };
+
+    div(
+        [
+            style! {
+                "position": "fixed",
+                "left": "50%",
+                "top": "40%",
+                "transform": "translate(-50%, -50%)",
+                "background": "#2b1d12",
+                "border": "3px solid black",
+                "padding": "16px",
+                "z-index": "200",
+                "width": "460px",
+                "font-family": "monospace",
+                "color": "white",
+                "cursor": "pointer",
+            },
+            on_click(|_| Msg::ContinueMishap),
+        ],
+        [
+            div([style! { "color": "#ffdd35", "margin-bottom": "10px" }], [text(game_state.say(&mishap.title))]),
+            div([], [text(game_state.say(page))]),
+            effects,
+            div(
+                [style! { "margin-top": "12px", "font-size": "12px", "color": "#ccc" }],
+                [text(format!("{} = {}", game_state.bindings.label(Action::Confirm), if last_page { "zapri" } else { "naprej" }))],
+            ),
+        ],
+    )
+}
+
+fn character_select_view(selection: Character) -> Node<Msg> {       //one card per character over the start background, click one to start (or pick with arrows and Enter)
+    let cards = ALL_CHARACTERS.iter().map(|character| {
+        let character = *character;
+        let (width, height) = character.size();
+        let quirks = character.quirks().iter().map(|quirk| {
+            div(
+                [style! { "margin-top": "6px", "font-size": "12px" }],
+                [
+                    div([style! { "color": "#ffdd35" }], [text(quirk.name())]),
+                    div([style! { "color": "#ccc" }], [text(quirk.description())]),
+                ],
+            )
+        });
+
+        div(
+            [
+                style! {
+                    "background": "#2b1d12",
+                    "border": if character == selection { "3px solid #ffdd35" } else { "3px solid black" },
+                    "padding": "16px",
+                    "width": "260px",
+                    "cursor": "pointer",
+                },
+                on_click(move |_| Msg::SelectCharacter(character)),
+            ],
+            [
+                div(
+                    [style! { "text-align": "center" }],
+                    [img(
+                        [
+                            attr("src", character.sprite(Smer::Stoji)),
+                            style! {
+                                "width": format!("{}px", width * 2.0),
+                                "height": format!("{}px", height * 2.0),
+                                "image-rendering": "pixelated",
+                            },
+                        ],
+                        [],
+                    )],
+                ),
+                div([style! { "font-size": "20px", "margin": "8px 0" }], [text(character.name())]),
+                div([style! { "font-size": "12px", "margin-bottom": "8px" }], [text(character.description())]),
+                div(
+                    [style! { "font-size": "12px", "color": "#ccc" }],
+                    [text(format!("{} € | pozornost {} | anksioznost {}", character.starting_money(), character.max_attention(), character.starting_anxiety()))],
+                ),
+                div([], quirks),
+            ],
+        )
+    });
+
+    div(
+        [
+            on_keydown(|event: KeyboardEvent| Msg::KeyDown(event.key())),
+            attr("tabindex", "0"),
+            style! {
+                "width": "100vw",
+                "height": "100vh",
+                "outline": "none",
+                "display": "flex",
+                "flex-direction": "column",
+                "align-items": "center",
+                "justify-content": "center",
+                "background-image": "url(/static/background/start/sunset_start.png)",
+                "background-size": "cover",
+                "color": "white",
+                "font-family": "monospace",
+            },
+        ],
+        [
+            div([style! { "font-size": "28px", "margin-bottom": "20px" }], [text("Izberi lika")]),
+            div([style! { "display": "flex", "gap": "24px" }], cards),
+        ],
+    )
+}
+
+fn endings_view(game_state: &GameState) -> Node<Msg> {     //every ending, the ones you haven't found yet stay hidden, click anywhere to go back
+    let entries = ALL_ENDINGS.iter().map(|ending| {
+        let found = game_state.endings.has(*ending);
+        let (picture, title, description) = if found {
+            (ending.art(), ending.title().to_string(), game_state.say(ending.text()))
+        } else {
+            ("/static/background/game_over_screen.png", "???".to_string(), "Še neodkrit konec.".to_string())
+        };
+
+        div(
+            [style! { "display": "flex", "gap": "12px", "align-items": "center", "margin-bottom": "10px" }],
+            [
+                img(
+                    [
+                        attr("src", picture),
+                        style! {
+                            "width": "96px",
+                            "height": "48px",
+                            "object-fit": "cover",
+                            "image-rendering": "pixelated",
+                            "filter": if found { "none" } else { "brightness(0.2)" },
+                        },
+                    ],
+                    [],
+                ),
+                div(
+                    [],
+                    [
+                        div([style! { "color": if found { "#ffdd35" } else { "#888" } }], [text(title)]),
+                        div([style! { "font-size": "12px", "color": "#ccc" }], [text(description)]),
+                    ],
+                ),
+            ],
+        )
+    });
+
+    div(
+        [
+            style! {
+                "width": "100vw",
+                "height": "100vh",
+                "display": "flex",
+                "flex-direction": "column",
+                "align-items": "center",
+                "justify-content": "center",
+                "background": "#1a1a2e",
+                "color": "white",
+                "font-family": "monospace",
+                "cursor": "pointer",
+            },
+            on_click(|_| Msg::Menu),
+        ],
+        [
+            div([style! { "font-size": "28px", "margin-bottom": "16px" }], [text(format!("Konci ({}/{})", game_state.endings.discovered.len(), ALL_ENDINGS.len()))]),
+            div([style! { "width": "560px" }], entries),
+            div([style! { "margin-top": "16px", "font-size": "12px" }], [text("Klikni za nazaj.")]),
+        ],
+    )
+}
+
+fn start_menu_button(label: String, msg: Msg) -> Node<Msg> {
+    div(
+        [
+            style! {
+                "background": "#2b1d12",
+                "border": "3px solid black",
+                "padding": "8px 16px",
+                "color": "white",
+                "font-family": "monospace",
+                "cursor": "pointer",
+            },
+            on_click(move |_| msg.clone()),
+        ],
+        [text(label)],
+    )
+}
+
+fn key_bindings_view(game_state: &GameState, waiting: Option<(Action, usize)>, from_pause: bool) -> Node<Msg> {      //one row per action, click a slot and press the new key
+    let rows = ALL_ACTIONS.iter().map(|action| {
+        let action = *action;
+        let keys = game_state.bindings.keys(action);
+        let slots = (0..KEYS_PER_ACTION).map(|slot| {
+            let selected = waiting == Some((action, slot));
+            let label = if selected {
+                "...".to_string()
+            } else {
+                keys.get(slot).map(|key| input::key_label(key)).unwrap_or_else(|| "-".to_string())
+            };
+            div(
+                [
+                    style! {
+                        "width": "110px",
+                        "text-align": "center",
+                        "padding": "4px",
+                        "cursor": "pointer",
+                        "background": if selected { "#ffdd35" } else { "#444" },
+                        "color": if selected { "black" } else { "white" },
+                    },
+                    on_click(move |_| Msg::RebindKey(action, slot)),
+                ],
+                [text(label)],
+            )
+        });
+
+        div(
+            [style! { "display": "flex", "align-items": "center", "gap": "10px", "margin-bottom": "6px" }],
+            [div([style! { "width": "140px" }], [text(action.label())]), div([style! { "display": "flex", "gap": "10px" }], slots)],
+        )
+    });
+
+    div(
+        [
+            on_keydown(|event: KeyboardEvent| Msg::KeyDown(event.key())),      //the new key comes in here
+            attr("tabindex", "0"),
+            style! {
+                "width": "100vw",
+                "height": "100vh",
+                "outline": "none",
+                "display": "flex",
+                "flex-direction": "column",
+                "align-items": "center",
+                "justify-content": "center",
+                "background": "#1a1a2e",
+                "color": "white",
+                "font-family": "monospace",
+            },
+        ],
+        [
+            div([style! { "font-size": "28px", "margin-bottom": "16px" }], [text("Tipke")]),
+            div([], rows),
+            div(
+                [style! { "margin-top": "12px", "font-size": "12px", "color": "#ccc" }],
+                [text(if waiting.is_some() { "Pritisni novo tipko (Esc = prekliči)." } else { "Klikni polje in pritisni novo tipko, Esc = nazaj." })],
+            ),
+            div(
+                [style! { "display": "flex", "gap": "12px", "margin-top": "16px" }],
+                [
+                    start_menu_button("Privzete tipke".to_string(), Msg::ResetKeyBindings),
+                    start_menu_button("Nazaj".to_string(), Msg::ShowSettings(from_pause)),
+                ],
+            ),
+        ],
+    )
+}
+
+fn settings_view(game_state: &GameState, selection: SettingsRow, from_pause: bool) -> Node<Msg> {     //one row per setting, arrows (or clicks on < >) change the value
+    let settings = &game_state.settings;
+    let rows = SETTINGS_ROWS.iter().map(|row| {
+        let row = *row;
+        let selected = row == selection;
+        let value = settings.value(row);
+        let arrow = |label: &'static str, step: i32| {
+            div(
+                [
+                    on_click(move |event: MouseEvent| {
+                        event.stop_propagation();       //the row itself would toggle it again
+                        Msg::ChangeSetting(row, step)
+                    }),
+                    style! { "padding": "0 6px", "cursor": "pointer" },
+                ],
+                [text(label)],
+            )
+        };
+        let controls = if value.is_empty() {
+            div([], [])
+        } else {
+            div([style! { "display": "flex", "align-items": "center" }], [arrow("<", -1), div([style! { "min-width": "110px", "text-align": "center" }], [text(value)]), arrow(">", 1)])
+        };
+
+        div(
+            [
+                on_click(move |_| Msg::SelectSettingsRow(row)),
+                style! {
+                    "display": "flex",
+                    "justify-content": "space-between",
+                    "align-items": "center",
+                    "gap": "24px",
+                    "padding": "6px 10px",
+                    "margin-bottom": "4px",
+                    "cursor": "pointer",
+                    "background": if selected { "#ffdd35" } else { "transparent" },
+                    "color": if selected { "black" } else { "white" },
+                },
+            ],
+            [div([], [text(settings.label(row))]), controls],
+        )
+    });
+
+    div(
+        [
+            on_keydown(|event: KeyboardEvent| Msg::KeyDown(event.key())),
+            attr("tabindex", "0"),
+            style! {
+                "width": "100vw",
+                "height": "100vh",
+                "outline": "none",
+                "display": "flex",
+                "flex-direction": "column",
+                "align-items": "center",
+                "justify-content": "center",
+                "background": if from_pause { "rgba(0, 0, 0, 0.85)" } else { "#1a1a2e" },
+                "color": "white",
+                "font-family": "monospace",
+            },
+        ],
+        [
+            div([style! { "font-size": "28px", "margin-bottom": "16px" }], [text(settings.language.pick("Nastavitve", "Settings"))]),
+            div([style! { "background": "#2b1d12", "border": "3px solid black", "padding": "16px", "min-width": "420px" }], rows),
+            div(
+                [style! { "margin-top": "12px", "font-size": "12px", "color": "#ccc" }],
+                [text(settings.language.pick("Puščici levo/desno spremenita vrednost, Esc = nazaj.", "Left/right changes the value, Esc = back."))],
+            ),
+        ],
+    )
+}
+
+fn achievements_view(game_state: &GameState) -> Node<Msg> {     //every dosežek with what you have to do, locked ones greyed out, click anywhere to go back
+    let entries = ALL_ACHIEVEMENTS.iter().map(|achievement| {
+        let unlocked = game_state.achievements.has(*achievement);
+        div(
+            [style! { "margin-bottom": "10px", "opacity": if unlocked { "1" } else { "0.4" } }],
+            [
+                div([style! { "color": if unlocked { "#ffdd35" } else { "white" } }], [text(format!("{} {}", if unlocked { "[x]" } else { "[ ]" }, game_state.say(achievement.title())))]),
+                div([style! { "font-size": "12px", "color": "#ccc", "padding-left": "28px" }], [text(achievement.description())]),
+            ],
+        )
+    });
+
+    div(
+        [
+            style! {
+                "width": "100vw",
+                "height": "100vh",
+                "display": "flex",
+                "flex-direction": "column",
+                "align-items": "center",
+                "justify-content": "center",
+                "background": "#1a1a2e",
+                "color": "white",
+                "font-family": "monospace",
+                "cursor": "pointer",
+            },
+            on_click(|_| Msg::Menu),
+        ],
+        [
+            div([style! { "font-size": "28px", "margin-bottom": "16px" }], [text(format!("Dosežki ({}/{})", game_state.achievements.unlocked.len(), ALL_ACHIEVEMENTS.len()))]),
+            div([style! { "width": "560px" }], entries),
+            div([style! { "margin-top": "16px", "font-size": "12px" }], [text("Klikni za nazaj.")]),
+        ],
+    )
+}
+
+fn pause_view(game_state: &GameState) -> Node<Msg> {        //dark veil over the frozen game and a box with the options, styled like the item menu
+    let Screen::Paused { selection } = game_state.screen else {
+        return div([], []);
+    };
+    let has_save = game_state.has_save;
+
+    let options = PAUSE_OPTIONS.iter().map(|option| {
+        let option = *option;
+        let selected = option == selection;
+        let disabled = option == PauseOption::Load && !has_save;
+        div(
+            [
+                on_click(move |_| Msg::SelectPauseOption(option)),
+                style! {
+                    "padding": "6px 10px",
+                    "margin-bottom": "4px",
+                    "cursor": "pointer",
+                    "background": if selected { "#ffdd35" } else { "transparent" },
+                    "color": if selected { "black" } else { "white" },
+                    "opacity": if disabled { "0.4" } else { "1" },
+                },
+            ],
+            [text(option.label(game_state.settings.language))],
+        )
+    });
+
+    div(
+        [style! {
+            "position": "fixed",
+            "top": "0",
+            "left": "0",
+            "width": "100vw",
+            "height": "100vh",
+            "background": "rgba(0, 0, 0, 0.6)",
+            "display": "flex",
+            "align-items": "center",
+            "justify-content": "center",
+            "z-index": "250",
+        }],
+        [div(
+            [style! {
+                "background": "#2b1d12",
+                "border": "3px solid black",
+                "padding": "16px",
+                "min-width": "220px",
+                "font-family": "monospace",
+                "color": "white",
+            }],
+            [
+                div([style! { "font-size": "20px", "margin-bottom": "12px", "color": "#ffdd35" }], [text(game_state.settings.language.pick("Pavza", "Paused"))]),
+                div([], options),
+                match &game_state.notification {        //"game saved" etc., the popup at the top is under the veil
+                    Some(notification) => div([style! { "margin-top": "10px", "font-size": "12px", "color": "#ccc" }], [text(&notification.text)]),
+                    None => div([], []),
+                },
+            ],
+        )],
+    )
+}
+
+//on-screen joystick and action buttons, only on touch devices (touch-action none so dragging doesn't scroll or zoom the page)
+fn touch_controls_view(game_state: &GameState) -> Node<Msg> {
+    if !game_state.touch.enabled {
+        return div([], []);
+    }
+    let diameter = JOYSTICK_RADIUS * 2.0;
+    let knob = 50.0;
+    let (x, y) = game_state.touch.stick;
+
+    let joystick = div(
+        [
+            on_touchstart(|event: TouchEvent| touch_stick(&event)),
+            on_touchmove(|event: TouchEvent| touch_stick(&event)),
+            on_touchend(|_| Msg::TouchStickEnd),
+            style! {
+                "position": "fixed",
+                "left": format!("{}px", JOYSTICK_MARGIN),
+                "bottom": format!("{}px", JOYSTICK_MARGIN),
+                "width": format!("{}px", diameter),
+                "height": format!("{}px", diameter),
+                "border-radius": "50%",
+                "background": "rgba(43, 29, 18, 0.5)",
+                "border": "3px solid black",
+                "touch-action": "none",
+                "z-index": "150",
+            },
+        ],
+        [div(
+            [style! {       //the knob follows the finger, but never leaves the circle
+                "position": "absolute",
+                "left": format!("{}px", JOYSTICK_RADIUS - knob / 2.0 + x * JOYSTICK_RADIUS),
+                "top": format!("{}px", JOYSTICK_RADIUS - knob / 2.0 + y * JOYSTICK_RADIUS),
+                "width": format!("{}px", knob),
+                "height": format!("{}px", knob),
+                "border-radius": "50%",
+                "background": "#ffdd35",
+                "border": "2px solid black",
+                "pointer-events": "none",
+            }],
+            [],
+        )],
+    );
+
+    let buttons = div(
+        [style! {
+            "position": "fixed",
+            "right": format!("{}px", JOYSTICK_MARGIN),
+            "bottom": format!("{}px", JOYSTICK_MARGIN),
+            "display": "grid",
+            "grid-template-columns": "repeat(2, 64px)",
+            "gap": "12px",
+            "z-index": "150",
+        }],
+        TOUCH_BUTTONS.iter().map(|(action, label)| {
+            let action = *action;
+            div(
+                [
+                    on_touchstart(move |_| Msg::Pressed(action)),
+                    style! {
+                        "width": "64px",
+                        "height": "64px",
+                        "border-radius": "50%",
+                        "background": "rgba(43, 29, 18, 0.7)",
+                        "border": "3px solid black",
+                        "color": "#ffdd35",
+                        "font-family": "monospace",
+                        "font-size": "18px",
+                        "display": "flex",
+                        "align-items": "center",
+                        "justify-content": "center",
+                        "touch-action": "none",
+                        "user-select": "none",
+                    },
+                ],
+                [text(*label)],
+            )
+        }),
+    );
+
+    div([], [joystick, buttons])
+}
+
+fn touch_stick(event: &TouchEvent) -> Msg {     //the finger that started on the joystick
+    match event.target_touches().get(0) {
+        Some(touch) => Msg::TouchStick(touch.client_x() as f64, touch.client_y() as f64),
+        None => Msg::TouchStickEnd,
+    }
+}
+
+fn toast_view(game_state: &GameState) -> Node<Msg> {       //"achievement unlocked" in the bottom right corner, on top of everything
+    let Some(toast) = game_state.toasts.first() else {
+        return div([], []);
+    };
+    div(
+        [
+            style! {
+                "position": "fixed",
+                "right": "20px",
+                "bottom": "20px",
+                "background": "#2b1d12",
+                "border": "2px solid #ffdd35",
+                "color": "#ffdd35",
+                "padding": "10px 16px",
+                "font-family": "monospace",
+                "z-index": "300",
+            },
+        ],
+        [text(&toast.text)],
+    )
+}
+
+fn graduated_view(game_state: &GameState) -> Node<Msg> {       //win screen, click anywhere to go back to the start
+    let transcript = &game_state.transcript;
+    let average = transcript.average().unwrap_or(0.0);
+    div(
+        [
+            style! {
+                "width": "100vw",
+                "height": "100vh",
+                "display": "flex",
+                "flex-direction": "column",
+                "align-items": "center",
+                "justify-content": "center",
+                "background": "#1a1a2e",
+                "color": "white",
+                "font-family": "monospace",
+                "cursor": "pointer",
+            },
+            on_click(|_| Msg::Menu),
+        ],
+        [
+            div([style! { "font-size": "32px", "margin-bottom": "16px" }], [text(game_state.say("[Diplomiral|Diplomirala] si!"))]),
+            div([], [text(format!("{} ECTS, povprečna ocena {:.2}", transcript.credits(), average))]),
+            div([style! { "margin-top": "8px" }], [text(format!("Vseh poskusov na izpitih: {}", transcript.attempts.len()))]),
+            div([style! { "margin-top": "24px", "font-size": "12px" }], [text("Klikni za nov začetek.")]),
+        ],
+    )
+}
+